@@ -0,0 +1,52 @@
+//! Benchmarks of every registered day on its real input, `./inputs/dayNN.txt`.
+//!
+//! Days without an input file, or whose solver fails, are skipped. Criterion options are
+//! passed after `--`, for example to only run a day against a saved baseline:
+//!
+//! ```sh
+//! cargo bench --bench days -- --save-baseline before
+//! cargo bench --bench days -- day06 --baseline before
+//! ```
+
+use advent_of_code_2024::{
+    input::Input,
+    solution::{Part, DAYS},
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn days(c: &mut Criterion) {
+    for day in DAYS {
+        let input = match Input::for_day(day.number).read() {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("skipping day {:02}: {:#}", day.number, e);
+                continue;
+            }
+        };
+
+        let mut group = c.benchmark_group(format!("day{:02}", day.number));
+
+        for &part in day.parts() {
+            if let Err(e) = day.run(part, &input) {
+                eprintln!("skipping day {:02} {}: {:#}", day.number, part, e);
+                continue;
+            }
+
+            let name = match part {
+                Part::One => "part_one",
+                Part::Two => "part_two",
+            };
+            group.bench_function(name, |b| b.iter(|| day.run(part, &input)));
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group! {
+    name = benches;
+    // Some solvers take hundreds of milliseconds, keep the default run time reasonable.
+    config = Criterion::default().sample_size(20);
+    targets = days
+}
+criterion_main!(benches);
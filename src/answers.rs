@@ -0,0 +1,163 @@
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::solution::{Answer, Part};
+
+/// Known-good answers of the real inputs, indexed by day.
+///
+/// ```toml
+/// [day01]
+/// part_one = 1234
+/// part_two = 5678
+///
+/// [day18]
+/// part_one = 300
+/// part_two = "12,34"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Answers {
+    days: BTreeMap<String, DayAnswers>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DayAnswers {
+    part_one: Option<AnswerValue>,
+    part_two: Option<AnswerValue>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum AnswerValue {
+    Integer(i64),
+    String(String),
+}
+
+impl AnswerValue {
+    fn to_answer(&self) -> String {
+        match self {
+            AnswerValue::Integer(i) => i.to_string(),
+            AnswerValue::String(s) => s.clone(),
+        }
+    }
+}
+
+impl Answers {
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read answers file {}", path.display()))?;
+
+        toml::from_str(&content)
+            .with_context(|| format!("failed to parse answers file {}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<String> {
+        let answers = self.days.get(&format!("day{:02}", day))?;
+        let value = match part {
+            Part::One => answers.part_one.as_ref(),
+            Part::Two => answers.part_two.as_ref(),
+        };
+        value.map(AnswerValue::to_answer)
+    }
+
+    /// Compare the answer of a part to the recorded one.
+    pub fn check(&self, day: u8, part: Part, answer: anyhow::Result<Answer>) -> Verdict {
+        match (self.get(day, part), answer) {
+            (_, Err(e)) => Verdict::Error(e),
+            (None, Ok(answer)) => Verdict::Missing {
+                actual: answer.value,
+            },
+            (Some(expected), Ok(answer)) if expected == answer.value => Verdict::Pass,
+            (Some(expected), Ok(answer)) => Verdict::Fail {
+                expected,
+                actual: answer.value,
+            },
+        }
+    }
+}
+
+/// Outcome of the verification of a part.
+#[derive(Debug)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing { actual: String },
+    Error(anyhow::Error),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "ok"),
+            Verdict::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {}, got {})", expected, actual)
+            }
+            Verdict::Missing { actual } => write!(f, "missing (got {})", actual),
+            Verdict::Error(e) => write!(f, "error ({:#})", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, time::Duration};
+
+    use anyhow::anyhow;
+
+    use super::{Answers, Verdict};
+    use crate::solution::{Answer, Part};
+
+    fn answer(value: &str) -> anyhow::Result<Answer> {
+        Ok(Answer {
+            value: value.to_string(),
+            parse_time: Duration::ZERO,
+            solve_time: Duration::ZERO,
+        })
+    }
+
+    #[test]
+    fn check() {
+        let path = std::env::temp_dir().join(format!("aoc-answers-{}.toml", std::process::id()));
+        fs::write(
+            &path,
+            "[day01]\npart_one = 1234\npart_two = 5678\n\n[day18]\npart_one = 300\npart_two = \"12,34\"\n",
+        )
+        .expect("failed to write answers file");
+        let answers = Answers::load(&path);
+        fs::remove_file(&path).expect("failed to remove answers file");
+        let answers = answers.expect("failed to load answers");
+
+        assert_eq!(Some("1234".to_string()), answers.get(1, Part::One));
+        assert_eq!(Some("12,34".to_string()), answers.get(18, Part::Two));
+
+        assert!(matches!(
+            answers.check(1, Part::One, answer("1234")),
+            Verdict::Pass
+        ));
+        assert!(matches!(
+            answers.check(18, Part::Two, answer("12,34")),
+            Verdict::Pass
+        ));
+        assert!(matches!(
+            answers.check(1, Part::Two, answer("5679")),
+            Verdict::Fail { expected, actual } if expected == "5678" && actual == "5679"
+        ));
+        assert!(matches!(
+            answers.check(2, Part::One, answer("42")),
+            Verdict::Missing { actual } if actual == "42"
+        ));
+        assert!(matches!(
+            answers.check(1, Part::One, Err(anyhow!("no input"))),
+            Verdict::Error(e) if e.to_string() == "no input"
+        ));
+    }
+
+    #[test]
+    fn committed_file() {
+        Answers::load("answers.toml").expect("failed to load answers.toml");
+    }
+}
@@ -1,47 +1,63 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    iter::zip,
-};
+use std::iter::zip;
 
 use regex::Regex;
 
-pub fn run_day() -> anyhow::Result<()> {
-    println!("=== DAY 01 ===");
-    let input_file =
-        BufReader::new(File::open("./inputs/day01.txt").expect("failed to open input file"));
+use crate::{error::ParseError, solution::Solution};
 
-    let lines = input_file.lines().map(|l| l.unwrap());
-    let res_part_one = part_one(lines)?;
+pub struct Day01;
 
-    println!("part one : {}", res_part_one);
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Historian Hysteria";
 
-    let res_part_two = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day01.txt").expect("failed to open input file"));
+    type Input = Lists;
+    type PartOne = i64;
+    type PartTwo = i64;
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_two(lines)?
-    };
+    fn parse<I>(lines: I) -> anyhow::Result<Lists>
+    where
+        I: Iterator<Item = String>,
+    {
+        parse(lines)
+    }
+
+    fn part_one(lists: &Lists) -> anyhow::Result<i64> {
+        part_one(lists)
+    }
 
-    println!("part two : {}", res_part_two);
+    fn part_two(lists: &Lists) -> anyhow::Result<i64> {
+        part_two(lists)
+    }
+}
 
-    Ok(())
+/// Both lists of location ids, in the order of the input.
+pub struct Lists {
+    first: Vec<i64>,
+    second: Vec<i64>,
 }
 
-fn part_one<I>(lines: I) -> anyhow::Result<i64>
+fn parse<I>(lines: I) -> anyhow::Result<Lists>
 where
     I: Iterator<Item = String>,
 {
-    let numbers = number_iter(lines)?;
-    let mut first_list = Vec::new();
-    let mut second_list = Vec::new();
+    let mut lists = Lists {
+        first: Vec::new(),
+        second: Vec::new(),
+    };
 
-    for (first, second) in numbers {
-        first_list.push(first);
-        second_list.push(second);
+    for pair in number_iter(lines)? {
+        let (first, second) = pair?;
+        lists.first.push(first);
+        lists.second.push(second);
     }
 
+    Ok(lists)
+}
+
+fn part_one(lists: &Lists) -> anyhow::Result<i64> {
+    let mut first_list = lists.first.clone();
+    let mut second_list = lists.second.clone();
+
     first_list.sort();
     second_list.sort();
 
@@ -52,26 +68,16 @@ where
     Ok(res)
 }
 
-fn part_two<I>(lines: I) -> anyhow::Result<i64>
-where
-    I: Iterator<Item = String>,
-{
-    let numbers = number_iter(lines)?;
-    let mut first_list = Vec::new();
-    let mut second_list = Vec::new();
-
-    for (first, second) in numbers {
-        first_list.push(first);
-        second_list.push(second);
-    }
-
-    let res = first_list
-        .into_iter()
+fn part_two(lists: &Lists) -> anyhow::Result<i64> {
+    let res = lists
+        .first
+        .iter()
         .map(|first| {
             first
-                * (second_list
+                * (lists
+                    .second
                     .iter()
-                    .filter(|second| **second == first)
+                    .filter(|second| *second == first)
                     .count() as i64)
         })
         .sum();
@@ -79,28 +85,36 @@ where
     Ok(res)
 }
 
-fn number_iter<I>(lines: I) -> anyhow::Result<impl Iterator<Item = (i64, i64)>>
+fn number_iter<I>(lines: I) -> anyhow::Result<impl Iterator<Item = Result<(i64, i64), ParseError>>>
 where
     I: Iterator<Item = String>,
 {
     let line_regex = Regex::new("^(\\d+)\\s+(\\d+)$")?;
 
-    let numbers = lines.filter_map(move |line| {
-        line_regex
-            .captures(&line)
-            .map(|capture| {
-                (
-                    capture.get(1).unwrap().as_str(),
-                    capture.get(2).unwrap().as_str(),
-                )
-            })
-            .map(|(first, second)| {
-                (
-                    first.parse::<i64>().unwrap(),
-                    second.parse::<i64>().unwrap(),
-                )
-            })
-    });
+    let numbers =
+        lines
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(move |(index, line)| {
+                let capture = line_regex.captures(&line).ok_or_else(|| {
+                    ParseError::new(Day01::DAY, index, 0, line.as_str(), "two numbers")
+                })?;
+
+                let parse = |i| {
+                    let number = capture.get(i).unwrap();
+                    number.as_str().parse::<i64>().map_err(|_| {
+                        ParseError::new(
+                            Day01::DAY,
+                            index,
+                            number.start(),
+                            number.as_str(),
+                            "an i64",
+                        )
+                    })
+                };
+
+                Ok((parse(1)?, parse(2)?))
+            });
     Ok(numbers)
 }
 
@@ -117,8 +131,9 @@ mod test {
             File::open("./inputs/day01-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
 
         assert_eq!(11, res);
     }
@@ -129,8 +144,9 @@ mod test {
             File::open("./inputs/day01-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_two(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_one");
 
         assert_eq!(31, res);
     }
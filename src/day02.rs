@@ -1,44 +1,67 @@
-use std::{
-    cmp::min,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use crate::{error::ParseError, solution::Solution};
 
-pub fn run_day() -> anyhow::Result<()> {
-    println!("=== DAY 02 ===");
-    let input_file =
-        BufReader::new(File::open("./inputs/day02.txt").expect("failed to open input file"));
+pub struct Day02;
 
-    let lines = input_file.lines().map(|l| l.unwrap());
-    let res_part_one = part_one(lines)?;
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Red-Nosed Reports";
 
-    println!("part one : {}", res_part_one);
+    type Input = Vec<Vec<i64>>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-    let res_part_two = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day02.txt").expect("failed to open input file"));
+    fn parse<I>(lines: I) -> anyhow::Result<Vec<Vec<i64>>>
+    where
+        I: Iterator<Item = String>,
+    {
+        Ok(parse_reports(lines)?)
+    }
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_two(lines)?
-    };
+    fn part_one(reports: &Vec<Vec<i64>>) -> anyhow::Result<usize> {
+        part_one(reports)
+    }
 
-    println!("part two : {}", res_part_two);
+    fn part_two(reports: &Vec<Vec<i64>>) -> anyhow::Result<usize> {
+        part_two(reports)
+    }
+}
 
-    Ok(())
+/// Levels of every report, one report per line.
+fn parse_reports<I>(lines: I) -> Result<Vec<Vec<i64>>, ParseError>
+where
+    I: Iterator<Item = String>,
+{
+    lines
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| {
+            let mut offset = 0;
+            line.split(' ')
+                .map(|level| {
+                    let level_offset = offset;
+                    offset += level.len() + 1;
+                    level.parse::<i64>().map_err(|_| {
+                        ParseError::new(Day02::DAY, index, level_offset, level, "a number")
+                    })
+                })
+                .collect()
+        })
+        .collect()
 }
 
 fn is_safe<I>(mut line: I) -> bool
 where
     I: Iterator<Item = i64>,
 {
-    let first = line.next().unwrap();
-    let mut prev = line.next().unwrap();
+    let (Some(first), Some(mut prev)) = (line.next(), line.next()) else {
+        return true;
+    };
 
     let diff = (prev - first).abs();
     if diff > 0 && diff < 4 {
         let is_decreasing = first > prev;
 
-        while let Some(next) = line.next() {
+        for next in line {
             let diff = (next - prev).abs();
             if diff > 0 && diff < 4 {
                 if next > prev && is_decreasing || next < prev && !is_decreasing {
@@ -55,14 +78,11 @@ where
     }
 }
 
-fn part_one<I>(lines: I) -> anyhow::Result<usize>
-where
-    I: Iterator<Item = String>,
-{
-    let res = lines
-        .filter_map(|l| {
-            let op = l.split(' ').map(|n| n.parse::<i64>().unwrap());
-            if is_safe(op) {
+fn part_one(reports: &[Vec<i64>]) -> anyhow::Result<usize> {
+    let res = reports
+        .iter()
+        .filter_map(|op| {
+            if is_safe(op.iter().copied()) {
                 Some(())
             } else {
                 None
@@ -91,22 +111,16 @@ fn is_safe_nice2(u: &[i64], consumed: bool, incr: i64, decr: i64, prev: i64) ->
 
         let i = !impossible && is_safe_nice2(&u[1..], consumed, incr, decr, value);
 
-        return i || (!consumed && is_safe_nice2(&u[1..], true, incr, decr, prev));
+        i || (!consumed && is_safe_nice2(&u[1..], true, incr, decr, prev))
     }
 }
 
-fn part_two<I>(lines: I) -> anyhow::Result<usize>
-where
-    I: Iterator<Item = String>,
-{
-    let res = lines
-        .filter_map(|l| {
-            let op = l
-                .split(' ')
-                .map(|n| n.parse::<i64>().unwrap())
-                .collect::<Vec<_>>();
-
-            let test = is_safe_nice2(&op[1..], false, 0, 0, op[0])
+fn part_two(reports: &[Vec<i64>]) -> anyhow::Result<usize> {
+    let res = reports
+        .iter()
+        .filter_map(|op| {
+            let test = op.len() < 3
+                || is_safe_nice2(&op[1..], false, 0, 0, op[0])
                 || is_safe_nice2(&op[2..], true, 0, 0, op[1]);
 
             if test {
@@ -133,8 +147,9 @@ mod test {
             File::open("./inputs/day02-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input = super::parse_reports(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
 
         assert_eq!(2, res);
     }
@@ -145,9 +160,19 @@ mod test {
             File::open("./inputs/day02-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_two(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input = super::parse_reports(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_one");
 
         assert_eq!(4, res);
     }
+
+    #[test]
+    fn invalid_level() {
+        let lines = ["7 6 4 2 1", "1 x 3"].map(String::from);
+
+        let err = super::parse_reports(lines.into_iter()).expect_err("input should be invalid");
+
+        assert_eq!((2, 3, "x"), (err.line, err.column, err.text.as_str()));
+    }
 }
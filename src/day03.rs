@@ -1,55 +1,59 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
-
 use regex::{Captures, Regex};
 
-pub fn run_day() -> anyhow::Result<()> {
-    println!("=== DAY 04 ===");
+use crate::solution::Solution;
 
-    let res_part_one = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day03.txt").expect("failed to open input file"));
+pub struct Day03;
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_one(lines)?
-    };
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Mull It Over";
 
-    println!("part one : {}", res_part_one);
+    type Input = Vec<Op>;
+    type PartOne = u64;
+    type PartTwo = u64;
 
-    let res_part_two = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day03.txt").expect("failed to open input file"));
+    fn parse<I>(lines: I) -> anyhow::Result<Vec<Op>>
+    where
+        I: Iterator<Item = String>,
+    {
+        parse(lines)
+    }
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_two(lines)?
-    };
+    fn part_one(ops: &Vec<Op>) -> anyhow::Result<u64> {
+        part_one(ops)
+    }
 
-    println!("part two : {}", res_part_two);
-    Ok(())
+    fn part_two(ops: &Vec<Op>) -> anyhow::Result<u64> {
+        part_two(ops)
+    }
 }
 
-fn part_one<I>(mut lines: I) -> anyhow::Result<u64>
+/// Instructions found in the corrupted memory, in order.
+fn parse<I>(lines: I) -> anyhow::Result<Vec<Op>>
 where
     I: Iterator<Item = String>,
 {
-    let line = lines.collect::<String>();
-    let regex = Regex::new(r"(?m)mul\((\d{1,3}),(\d{1,3})\)")?;
+    let regex = Regex::new(r"(?m)mul\((\d{1,3}),(\d{1,3})\)|(don't)|(do)")?;
 
-    let result = regex.captures_iter(&line);
+    regex
+        .captures_iter(&lines.collect::<String>())
+        .map(Op::try_from)
+        .collect()
+}
 
-    let mut res = 0;
+fn part_one(ops: &[Op]) -> anyhow::Result<u64> {
+    let res = ops
+        .iter()
+        .map(|op| match op {
+            Op::Mul(a, b) => a * b,
+            _ => 0,
+        })
+        .sum();
 
-    for mat in result {
-        let first_parameter = mat.get(1).unwrap().as_str().parse::<u64>()?;
-        let second_parameter = mat.get(2).unwrap().as_str().parse::<u64>()?;
-        res += first_parameter * second_parameter;
-    }
     Ok(res)
 }
 
-enum Op {
+pub enum Op {
     Mul(u64, u64),
     Enable,
     Disable,
@@ -59,9 +63,9 @@ impl TryFrom<Captures<'_>> for Op {
     type Error = anyhow::Error;
 
     fn try_from(mat: Captures<'_>) -> Result<Self, Self::Error> {
-        if let Some(_) = mat.get(3) {
+        if mat.get(3).is_some() {
             Ok(Op::Disable)
-        } else if let Some(_) = mat.get(4) {
+        } else if mat.get(4).is_some() {
             Ok(Op::Enable)
         } else {
             let first = mat
@@ -79,16 +83,9 @@ impl TryFrom<Captures<'_>> for Op {
     }
 }
 
-fn part_two<I>(lines: I) -> anyhow::Result<u64>
-where
-    I: Iterator<Item = String>,
-{
-    let regex = Regex::new(r"(?m)mul\((\d{1,3}),(\d{1,3})\)|(don't)|(do)")?;
-
-    let res = regex
-        .captures_iter(&lines.collect::<String>())
-        .map(|m| Op::try_from(m))
-        .flatten()
+fn part_two(ops: &[Op]) -> anyhow::Result<u64> {
+    let res = ops
+        .iter()
         .scan(true, |mul_enabled, op| {
             match op {
                 Op::Mul(a, b) if *mul_enabled => return Some(a * b),
@@ -96,7 +93,7 @@ where
                 Op::Disable => *mul_enabled = false,
                 _ => (),
             }
-            return Some(0);
+            Some(0)
         })
         .sum();
 
@@ -116,8 +113,9 @@ mod test {
             File::open("./inputs/day03-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
 
         assert_eq!(161, res);
     }
@@ -128,8 +126,9 @@ mod test {
             File::open("./inputs/day03-02-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_two(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_one");
 
         assert_eq!(48, res);
     }
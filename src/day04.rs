@@ -1,132 +1,75 @@
-use std::{
-    cmp::min,
-    fs::File,
-    io::{BufRead, BufReader},
+use crate::{
+    grid::{Grid, Point},
+    solution::Solution,
 };
 
-pub fn run_day() -> anyhow::Result<()> {
-    println!("=== DAY 04 ===");
+pub struct Day04;
 
-    let res_part_one = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day04.txt").expect("failed to open input file"));
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Ceres Search";
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_one(lines)?
-    };
-
-    println!("part one : {}", res_part_one);
-
-    let res_part_two = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day04.txt").expect("failed to open input file"));
-
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_two(lines)?
-    };
+    type Input = Grid<char>;
+    type PartOne = u64;
+    type PartTwo = u64;
 
-    println!("part two : {}", res_part_two);
-    Ok(())
-}
-fn part_one<I>(lines: I) -> anyhow::Result<u64>
-where
-    I: Iterator<Item = String>,
-{
-    let mut grid = lines
-        .map(|l| l.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-    let mut res = 0;
-
-    for y in 0..grid.len() {
-        for x in 0..grid.len() {
-            if check_down(&mut grid, x, y) {
-                res += 1;
-            }
-            if check_right(&mut grid, x, y) {
-                res += 1;
-            }
-            if check_diag_bottom(&mut grid, x, y) {
-                res += 1;
-            }
-            if check_diag_top(&mut grid, x, y) {
-                res += 1;
-            }
-        }
+    fn parse<I>(lines: I) -> anyhow::Result<Grid<char>>
+    where
+        I: Iterator<Item = String>,
+    {
+        Grid::from_lines(lines)
     }
 
-    Ok(res)
-}
-
-fn check_down(grid: &Vec<Vec<char>>, x: usize, y: usize) -> bool {
-    let end = min(grid.len(), y + 4) - y;
-    if end < 4 {
-        false
-    } else {
-        let other = (0..end).map(|e| grid[y + e][x]).collect::<Vec<_>>();
-
-        return other == ['X', 'M', 'A', 'S'] || other == ['S', 'A', 'M', 'X'];
+    fn part_one(grid: &Grid<char>) -> anyhow::Result<u64> {
+        part_one(grid)
     }
-}
 
-fn check_right(grid: &Vec<Vec<char>>, x: usize, y: usize) -> bool {
-    let end = min(grid[y].len(), x + 4) - x;
-    if end < 4 {
-        false
-    } else {
-        let other = (0..end).map(|e| grid[y][x + e]).collect::<Vec<_>>();
-        return other == ['X', 'M', 'A', 'S'] || other == ['S', 'A', 'M', 'X'];
+    fn part_two(grid: &Grid<char>) -> anyhow::Result<u64> {
+        part_two(grid)
     }
 }
 
-fn check_diag_bottom(grid: &Vec<Vec<char>>, x: usize, y: usize) -> bool {
-    let end = min(min(grid[y].len(), x + 4) - x, min(grid.len(), y + 4) - y);
+fn part_one(grid: &Grid<char>) -> anyhow::Result<u64> {
+    let mut res = 0;
 
-    if end < 4 {
-        false
-    } else {
-        let other = (0..end).map(|e| grid[y + e][x + e]).collect::<Vec<_>>();
-        return other == ['X', 'M', 'A', 'S'] || other == ['S', 'A', 'M', 'X'];
-    }
-}
+    for start in grid.points() {
+        if grid[start] != 'X' {
+            continue;
+        }
 
-fn check_diag_top(grid: &Vec<Vec<char>>, x: usize, y: usize) -> bool {
-    if y < 3 {
-        return false;
+        for step in Point::ADJACENT {
+            let word = std::iter::once(start)
+                .chain(grid.ray(start, step))
+                .take(4)
+                .map(|p| grid[p]);
+            if word.eq(['X', 'M', 'A', 'S']) {
+                res += 1;
+            }
+        }
     }
-    let end = min(min(grid[y].len(), x + 4) - x, y + 4);
-
-    if end < 4 {
-        false
-    } else {
-        let other = (0..end).map(|e| grid[y - e][x + e]).collect::<Vec<_>>();
 
-        return other == ['X', 'M', 'A', 'S'] || other == ['S', 'A', 'M', 'X'];
-    }
+    Ok(res)
 }
 
-fn part_two<I>(lines: I) -> anyhow::Result<u64>
-where
-    I: Iterator<Item = String>,
-{
-    let grid = lines
-        .map(|l| l.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
+fn part_two(grid: &Grid<char>) -> anyhow::Result<u64> {
     let mut res = 0;
 
-    for y in 1..grid.len() - 1 {
-        for x in 1..grid[0].len() - 1 {
-            let mut is_good = grid[y][x] == 'A';
-            is_good = is_good
-                && ((grid[y - 1][x - 1] == 'M' && grid[y + 1][x + 1] == 'S')
-                    || (grid[y - 1][x - 1] == 'S' && grid[y + 1][x + 1] == 'M'));
-
-            is_good = is_good
-                && ((grid[y - 1][x + 1] == 'M' && grid[y + 1][x - 1] == 'S')
-                    || (grid[y - 1][x + 1] == 'S' && grid[y + 1][x - 1] == 'M'));
+    for center in grid.points() {
+        if grid[center] != 'A' {
+            continue;
+        }
 
-            if is_good {
-                res += 1;
-            }
+        let is_mas = |a: Point, b: Point| {
+            matches!(
+                (grid.get(center + a), grid.get(center + b)),
+                (Some('M'), Some('S')) | (Some('S'), Some('M'))
+            )
+        };
+
+        if is_mas(Point::new(-1, -1), Point::new(1, 1))
+            && is_mas(Point::new(1, -1), Point::new(-1, 1))
+        {
+            res += 1;
         }
     }
 
@@ -146,8 +89,9 @@ mod test {
             File::open("./inputs/day04-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input = super::Grid::from_lines(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
 
         assert_eq!(18, res);
     }
@@ -158,8 +102,9 @@ mod test {
             File::open("./inputs/day04-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_two(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input = super::Grid::from_lines(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_one");
 
         assert_eq!(9, res);
     }
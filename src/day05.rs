@@ -1,33 +1,54 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
-
 use anyhow::anyhow;
 
-pub fn run_day() -> anyhow::Result<()> {
-    println!("=== DAY 04 ===");
+use crate::solution::Solution;
 
-    let res_part_one = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day05.txt").expect("failed to open input file"));
+pub struct Day05;
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_one(lines)?
-    };
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Print Queue";
+
+    type Input = PrintQueue;
+    type PartOne = u64;
+    type PartTwo = u64;
 
-    println!("part one : {}", res_part_one);
+    fn parse<I>(lines: I) -> anyhow::Result<PrintQueue>
+    where
+        I: Iterator<Item = String>,
+    {
+        parse(lines)
+    }
 
-    let res_part_two = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day05.txt").expect("failed to open input file"));
+    fn part_one(queue: &PrintQueue) -> anyhow::Result<u64> {
+        part_one(queue)
+    }
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_two(lines)?
-    };
+    fn part_two(queue: &PrintQueue) -> anyhow::Result<u64> {
+        part_two(queue)
+    }
+}
+
+/// Page ordering rules followed by the updates to print.
+pub struct PrintQueue {
+    rules: Vec<(u64, u64)>,
+    updates: Vec<Vec<u64>>,
+}
+
+fn parse<I>(mut lines: I) -> anyhow::Result<PrintQueue>
+where
+    I: Iterator<Item = String>,
+{
+    let rules = get_rules(&mut lines)?;
+    let updates = lines
+        .map(|update| {
+            update
+                .split(',')
+                .filter_map(|c| c.parse::<u64>().ok())
+                .collect::<Vec<_>>()
+        })
+        .collect();
 
-    println!("part two : {}", res_part_two);
-    Ok(())
+    Ok(PrintQueue { rules, updates })
 }
 
 fn get_rules<I>(lines: &mut I) -> anyhow::Result<Vec<(u64, u64)>>
@@ -64,9 +85,7 @@ fn is_rule_matched(update: &[u64], rules: &[(u64, u64)]) -> bool {
             .iter()
             .enumerate()
             .find_map(|(i, r)| if r == r2 { Some(i) } else { None });
-        if c1.is_some() && c2.is_some() {
-            let (c1, c2) = (c1.unwrap(), c2.unwrap());
-
+        if let (Some(c1), Some(c2)) = (c1, c2) {
             if c1 > c2 {
                 return false;
             }
@@ -74,49 +93,29 @@ fn is_rule_matched(update: &[u64], rules: &[(u64, u64)]) -> bool {
     }
     true
 }
-fn part_one<I>(mut lines: I) -> anyhow::Result<u64>
-where
-    I: Iterator<Item = String>,
-{
-    let rules = get_rules(&mut lines)?;
-    let res = lines
-        .map(|update| {
-            update
-                .split(',')
-                .filter_map(|c| c.parse::<u64>().ok())
-                .collect::<Vec<_>>()
-        })
-        .filter_map(|update| {
-            if is_rule_matched(&update, &rules) {
-                Some(update)
-            } else {
-                None
-            }
-        })
+fn part_one(queue: &PrintQueue) -> anyhow::Result<u64> {
+    let rules = &queue.rules;
+    let res = queue
+        .updates
+        .iter()
+        .filter(|update| is_rule_matched(update, rules))
         .map(|l| l[l.len() / 2])
         .sum();
 
     Ok(res)
 }
 
-fn part_two<I>(mut lines: I) -> anyhow::Result<u64>
-where
-    I: Iterator<Item = String>,
-{
-    let rules = get_rules(&mut lines)?;
+fn part_two(queue: &PrintQueue) -> anyhow::Result<u64> {
+    let rules = &queue.rules;
 
-    let res = lines
-        .map(|update| {
-            update
-                .split(',')
-                .filter_map(|c| c.parse::<u64>().ok())
-                .collect::<Vec<_>>()
-        })
+    let res = queue
+        .updates
+        .iter()
         .filter_map(|update| {
-            if is_rule_matched(&update, &rules) {
+            if is_rule_matched(update, rules) {
                 None
             } else {
-                Some(update)
+                Some(update.clone())
             }
         })
         .map(|mut update| {
@@ -134,9 +133,7 @@ where
                             .iter()
                             .enumerate()
                             .find_map(|(i, r)| if r == r2 { Some(i) } else { None });
-                    if c1.is_some() && c2.is_some() {
-                        let (c1, c2) = (c1.unwrap(), c2.unwrap());
-
+                    if let (Some(c1), Some(c2)) = (c1, c2) {
                         if c1 > c2 {
                             work = true;
 
@@ -166,8 +163,9 @@ mod test {
             File::open("./inputs/day05-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
 
         assert_eq!(143, res);
     }
@@ -178,8 +176,9 @@ mod test {
             File::open("./inputs/day05-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_two(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_one");
 
         assert_eq!(123, res);
     }
@@ -1,35 +1,36 @@
-use std::collections::{HashMap, HashSet};
-use std::time::Duration;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
-
-use fxhash::{FxHashSet, FxHasher};
+use fxhash::{FxHashMap, FxHashSet};
+use rayon::prelude::*;
 
-pub fn run_day() -> anyhow::Result<()> {
-    println!("=== DAY 06 ===");
+use crate::{
+    error::ParseError,
+    grid::{Direction, Grid, Point},
+    solution::Solution,
+};
 
-    let res_part_one = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day06.txt").expect("failed to open input file"));
+pub struct Day06;
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_one(lines)?
-    };
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Guard Gallivant";
 
-    println!("part one : {}", res_part_one);
+    type Input = Lab;
+    type PartOne = u64;
+    type PartTwo = u64;
 
-    let res_part_two = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day06.txt").expect("failed to open input file"));
+    fn parse<I>(lines: I) -> anyhow::Result<Lab>
+    where
+        I: Iterator<Item = String>,
+    {
+        parse(lines)
+    }
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_two(lines)?
-    };
+    fn part_one(lab: &Lab) -> anyhow::Result<u64> {
+        part_one(lab, Edges::Exit)
+    }
 
-    println!("part two : {}", res_part_two);
-    Ok(())
+    fn part_two(lab: &Lab) -> anyhow::Result<u64> {
+        part_two(lab, Edges::Exit)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,268 +40,419 @@ enum CellType {
     Guard(Direction),
 }
 
-#[repr(u8)]
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
-enum Direction {
-    Up = 0,
-    Left = 1,
-    Right = 2,
-    Down = 3,
-}
+type GuardState = (Point, Direction);
 
-fn part_one<I>(lines: I) -> anyhow::Result<u64>
-where
-    I: Iterator<Item = String>,
-{
-    let mut map = extract_map(lines);
-
-    // Find guard position
-    let mut guard_states = Vec::new();
-    for y in 0..map.len() {
-        for x in 0..map[y].len() {
-            if let CellType::Guard(dir) = map[y][x] {
-                guard_states.push((x, y, dir));
-                map[y][x] = CellType::Empty;
-            }
-        }
-    }
-
-    let mut nbr = 1;
-    loop {
-        let (last_x, last_y, last_dir) = *guard_states.last().unwrap();
-        let new_state = match last_dir {
-            Direction::Up => {
-                if last_y == 0 {
-                    break;
-                } else if map[last_y - 1][last_x] != CellType::Wall {
-                    (last_x, last_y - 1, last_dir)
-                } else {
-                    (last_x, last_y, Direction::Right)
-                }
-            }
-            Direction::Left => {
-                if last_x == 0 {
-                    break;
-                } else if map[last_y][last_x - 1] != CellType::Wall {
-                    (last_x - 1, last_y, last_dir)
-                } else {
-                    (last_x, last_y, Direction::Up)
-                }
-            }
-            Direction::Right => {
-                if last_x + 1 == map[last_y].len() {
-                    break;
-                } else if map[last_y][last_x + 1] != CellType::Wall {
-                    (last_x + 1, last_y, last_dir)
-                } else {
-                    (last_x, last_y, Direction::Down)
-                }
-            }
-            Direction::Down => {
-                if last_y + 1 == map.len() {
-                    break;
-                } else if map[last_y + 1][last_x] != CellType::Wall {
-                    (last_x, last_y + 1, last_dir)
-                } else {
-                    (last_x, last_y, Direction::Left)
-                }
-            }
-        };
+/// Map of the lab, with the guards removed from it.
+pub struct Lab {
+    map: Grid<CellType>,
+    /// Initial states of the guards, in reading order.
+    guards: Vec<GuardState>,
+}
 
-        if !guard_states
-            .iter()
-            .any(|s| s.0 == new_state.0 && s.1 == new_state.1)
-        {
-            nbr += 1;
+impl Lab {
+    /// Initial state of the only guard of the lab.
+    fn guard(&self) -> anyhow::Result<GuardState> {
+        match self.guards[..] {
+            [guard] => Ok(guard),
+            _ => anyhow::bail!("expected a single guard, found {}", self.guards.len()),
         }
-
-        guard_states.push(new_state);
     }
-
-    Ok(nbr)
 }
 
-fn extract_map<I>(lines: I) -> Vec<Vec<CellType>>
+/// Lab described by the lines of the puzzle input, with at least one guard.
+pub fn parse<I>(lines: I) -> anyhow::Result<Lab>
 where
     I: Iterator<Item = String>,
 {
-    lines
-        .take_while(|c| !c.is_empty())
-        .map(|c| {
-            c.chars()
-                .map(|c| {
-                    if c == '#' {
-                        CellType::Wall
-                    } else if c == '.' {
-                        CellType::Empty
-                    } else {
-                        let dir = match c {
-                            '^' => Direction::Up,
-                            '>' => Direction::Right,
-                            '<' => Direction::Left,
-                            'v' => Direction::Down,
-                            _ => panic!("invalid dir"),
-                        };
-
-                        CellType::Guard(dir)
-                    }
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>()
+    let (map, guards) = extract_guards(extract_map(lines)?)?;
+
+    Ok(Lab { map, guards })
 }
 
-fn part_two<I>(lines: I) -> anyhow::Result<u64>
+/// Number of cells visited by the guards, with `edges` deciding what happens past the edges of
+/// the map.
+pub fn part_one(lab: &Lab, edges: Edges) -> anyhow::Result<u64> {
+    let patrol = patrol(&lab.map, &lab.guards, edges);
+
+    Ok(patrol.visited.len() as u64)
+}
+
+fn extract_map<I>(lines: I) -> anyhow::Result<Grid<CellType>>
 where
     I: Iterator<Item = String>,
 {
-    let mut map = extract_map(lines);
-
-    // Find guard position
-    let mut guard_states = Vec::new();
-    for y in 0..map.len() {
-        for x in 0..map[y].len() {
-            if let CellType::Guard(dir) = map[y][x] {
-                guard_states.push((x, y, dir));
-                map[y][x] = CellType::Empty;
+    let map = Grid::from_lines(lines)?.try_map(|p, c| {
+        let dir = match c {
+            '#' => return Ok(CellType::Wall),
+            '.' => return Ok(CellType::Empty),
+            '^' => Direction::Up,
+            '>' => Direction::Right,
+            '<' => Direction::Left,
+            'v' => Direction::Down,
+            _ => {
+                return Err(ParseError::new(
+                    Day06::DAY,
+                    p.y as usize,
+                    p.x as usize,
+                    *c,
+                    "one of `#`, `.`, `^`, `>`, `<` or `v`",
+                ))
             }
-        }
+        };
+
+        Ok(CellType::Guard(dir))
+    })?;
+    Ok(map)
+}
+
+/// Remove the guards from the map, returning their initial states in reading order.
+fn extract_guards(mut map: Grid<CellType>) -> anyhow::Result<(Grid<CellType>, Vec<GuardState>)> {
+    let guards = map
+        .iter()
+        .filter_map(|(p, c)| match c {
+            CellType::Guard(dir) => Some((p, *dir)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if guards.is_empty() {
+        anyhow::bail!("no guard on the map");
     }
 
-    let possible_block_positions = get_possible_positions(&map, &guard_states);
+    for (position, _) in &guards {
+        map[*position] = CellType::Empty;
+    }
 
-    let state = *guard_states.last().unwrap();
+    Ok((map, guards))
+}
 
-    let mut guard_states_set = FxHashSet::default();
+/// Next state of the guard, or `None` once it left the map.
+fn step(map: &Grid<CellType>, (position, dir): GuardState) -> Option<GuardState> {
+    let next = position + dir;
+    match map.get(next)? {
+        CellType::Wall => Some((position, dir.turn_right())),
+        _ => Some((next, dir)),
+    }
+}
 
-    let mut nbr = 0;
-    for (x, y) in possible_block_positions.iter().skip(1) {
-        let (x, y) = (*x, *y);
-        if map[y][x] == CellType::Wall || (x == state.0 && y == state.1) {
-            continue;
-        }
+/// States of the guard until it leaves the map, starting with `start`.
+///
+/// A guard walking in a loop would never leave, so the walk stops right before the first state
+/// seen twice.
+fn walk(map: &Grid<CellType>, start: GuardState) -> impl Iterator<Item = GuardState> + '_ {
+    let mut seen = FxHashSet::default();
+    std::iter::successors(Some(start), |state| step(map, *state))
+        .take_while(move |state| seen.insert(*state))
+}
 
-        let mut map = map.iter().map(|m| m.clone()).collect::<Vec<_>>();
+/// What happens to a guard walking past the edge of the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edges {
+    /// The guard leaves the map, as in the puzzle.
+    Exit,
+    /// The guard comes back from the opposite edge, the map being a torus.
+    Wrap,
+}
 
-        let mut last_state = *guard_states.first().unwrap();
+impl Edges {
+    /// Cell in front of a guard at `position` facing `dir`, `None` if it is off the map.
+    fn next(self, map: &Grid<CellType>, position: Point, dir: Direction) -> Option<Point> {
+        let next = position + dir;
+        match self {
+            Edges::Exit => map.contains(next).then_some(next),
+            Edges::Wrap => Some(Point::new(
+                next.x.rem_euclid(map.width() as i64),
+                next.y.rem_euclid(map.height() as i64),
+            )),
+        }
+    }
+}
 
-        guard_states_set.clear();
-        guard_states_set.insert(last_state);
+/// Result of simulating every guard together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Patrol {
+    /// Cells visited by at least one guard.
+    pub visited: FxHashSet<Point>,
+    /// Whether the guards ended up repeating themselves instead of all leaving the map.
+    pub looped: bool,
+}
 
-        map[y][x] = CellType::Wall;
+/// Simulate every guard at once, until they all left the map or are stuck in a loop.
+///
+/// Guards move one after the other, in the order of `guards`, and treat the cells occupied by
+/// the other guards as obstacles. A guard wrapping around a one-cell wide map onto its own cell
+/// does not block itself. A loop is a joint state of every guard seen twice.
+fn patrol(map: &Grid<CellType>, guards: &[GuardState], edges: Edges) -> Patrol {
+    let mut guards = guards.iter().copied().map(Some).collect::<Vec<_>>();
+    let mut visited = guards
+        .iter()
+        .flatten()
+        .map(|(p, _)| *p)
+        .collect::<FxHashSet<_>>();
+    let mut seen = FxHashSet::default();
+
+    while guards.iter().any(Option::is_some) {
+        if !seen.insert(guards.clone()) {
+            return Patrol {
+                visited,
+                looped: true,
+            };
+        }
 
-        let stuck = loop {
-            let (last_x, last_y, last_dir) = last_state;
-            let new_state = match last_dir {
-                Direction::Up => {
-                    if last_y == 0 {
-                        break false;
-                    } else if map[last_y - 1][last_x] != CellType::Wall {
-                        (last_x, last_y - 1, last_dir)
-                    } else {
-                        (last_x, last_y, Direction::Right)
-                    }
-                }
-                Direction::Left => {
-                    if last_x == 0 {
-                        break false;
-                    } else if map[last_y][last_x - 1] != CellType::Wall {
-                        (last_x - 1, last_y, last_dir)
-                    } else {
-                        (last_x, last_y, Direction::Up)
-                    }
-                }
-                Direction::Right => {
-                    if last_x + 1 == map[last_y].len() {
-                        break false;
-                    } else if map[last_y][last_x + 1] != CellType::Wall {
-                        (last_x + 1, last_y, last_dir)
-                    } else {
-                        (last_x, last_y, Direction::Down)
-                    }
+        for i in 0..guards.len() {
+            let Some((position, dir)) = guards[i] else {
+                continue;
+            };
+
+            guards[i] = match edges.next(map, position, dir) {
+                None => None,
+                Some(next)
+                    if map[next] == CellType::Wall
+                        || guards
+                            .iter()
+                            .enumerate()
+                            .any(|(j, guard)| j != i && guard.is_some_and(|(p, _)| p == next)) =>
+                {
+                    Some((position, dir.turn_right()))
                 }
-                Direction::Down => {
-                    if last_y + 1 == map.len() {
-                        break false;
-                    } else if map[last_y + 1][last_x] != CellType::Wall {
-                        (last_x, last_y + 1, last_dir)
-                    } else {
-                        (last_x, last_y, Direction::Left)
-                    }
+                Some(next) => {
+                    visited.insert(next);
+                    Some((next, dir))
                 }
             };
+        }
+    }
+
+    Patrol {
+        visited,
+        looped: false,
+    }
+}
+
+/// Cells visited by the guards of the map, and whether they loop, with `edges` deciding what
+/// happens past the edges of the map.
+pub fn simulate(lab: &Lab, edges: Edges) -> Patrol {
+    patrol(&lab.map, &lab.guards, edges)
+}
+
+/// Number of positions where a single obstacle makes the guards loop, with `edges` deciding what
+/// happens past the edges of the map.
+pub fn part_two(lab: &Lab, edges: Edges) -> anyhow::Result<u64> {
+    let Lab { map, guards } = lab;
+
+    // The jump tables assume that the guard leaves the map at its edges.
+    let nbr = if let ([start], Edges::Exit) = (&guards[..], edges) {
+        let start = *start;
+        let jumps = Jumps::new(map);
+        get_possible_positions(map, start)
+            .into_par_iter()
+            .filter(|obstacle| jumps.loop_length(start, *obstacle).is_some())
+            .count()
+    } else {
+        // Guards block each other, so the whole patrol is simulated for every obstacle.
+        patrol(map, guards, edges)
+            .visited
+            .into_par_iter()
+            .filter(|obstacle| !guards.iter().any(|(p, _)| p == obstacle))
+            .filter(|obstacle| {
+                let mut map = map.clone();
+                map[*obstacle] = CellType::Wall;
+                patrol(&map, guards, edges).looped
+            })
+            .count()
+    };
+
+    Ok(nbr as u64)
+}
+
+/// For every cell and direction, where the guard stops before the next wall, so that walking a
+/// straight segment is a single lookup.
+struct Jumps {
+    /// Indexed by direction, `None` when the guard leaves the map instead.
+    stops: [Grid<Option<Point>>; 4],
+}
+
+impl Jumps {
+    fn new(map: &Grid<CellType>) -> Self {
+        let points = map.points().collect::<Vec<_>>();
+
+        let stops = Direction::ALL.map(|dir| {
+            let mut stops = Grid::new(map.width(), map.height(), None);
 
-            if guard_states_set.contains(&new_state) {
-                break true;
+            // The stop of a cell depends on the one of its neighbour in `dir`, which must be
+            // computed first.
+            let ordered: Box<dyn Iterator<Item = &Point>> = match dir {
+                Direction::Up | Direction::Left => Box::new(points.iter()),
+                Direction::Down | Direction::Right => Box::new(points.iter().rev()),
+            };
+            for &position in ordered {
+                let next = position + dir;
+                stops[position] = match map.get(next) {
+                    None => None,
+                    Some(CellType::Wall) => Some(position),
+                    Some(_) => stops[next],
+                };
             }
 
-            last_state = new_state;
+            stops
+        });
 
-            guard_states_set.insert(new_state);
-        };
+        Self { stops }
+    }
+
+    /// State of the guard at its next turn, or `None` once it left the map, with an extra
+    /// obstacle at `obstacle`.
+    ///
+    /// The obstacle only matters when it lies on the segment walked from `position`, in which
+    /// case the guard stops right before it.
+    fn jump(&self, (position, dir): GuardState, obstacle: Point) -> Option<GuardState> {
+        let stop = self.stops[dir as usize][position];
+
+        let offset = dir.offset();
+        let delta = obstacle - position;
+        let distance = delta.x * offset.x + delta.y * offset.y;
+        let on_segment = distance > 0
+            && delta == offset * distance
+            && stop.is_none_or(|stop| distance <= stop.manhattan(position));
+
+        if on_segment {
+            Some((obstacle - offset, dir.turn_right()))
+        } else {
+            stop.map(|stop| (stop, dir.turn_right()))
+        }
+    }
 
-        if stuck {
-            nbr += 1;
+    /// Number of steps of the loop the guard walks in once `obstacle` is placed, or `None` if
+    /// it leaves the map.
+    fn loop_length(&self, start: GuardState, obstacle: Point) -> Option<usize> {
+        // Steps walked before reaching each turn.
+        let mut turns = FxHashMap::default();
+        let mut steps = 0;
+
+        let mut state = start;
+        while let Some(next) = self.jump(state, obstacle) {
+            steps += next.0.manhattan(state.0) as usize;
+            if let Some(first) = turns.insert(next, steps) {
+                return Some(steps - first);
+            }
+            state = next;
         }
+
+        None
     }
+}
 
-    Ok(nbr)
+/// Empty positions visited by the guard, where an obstacle could be placed.
+fn get_possible_positions(map: &Grid<CellType>, start: GuardState) -> Vec<Point> {
+    let mut positions = Vec::new();
+    let mut seen = FxHashSet::default();
+    for (position, _) in walk(map, start) {
+        if position != start.0 && seen.insert(position) {
+            positions.push(position);
+        }
+    }
+    positions
 }
 
-fn get_possible_positions(
-    map: &Vec<Vec<CellType>>,
-    guard_states: &Vec<(usize, usize, Direction)>,
-) -> Vec<(usize, usize)> {
-    let mut last_state = *guard_states.first().unwrap();
-    let mut guard_states = Vec::new();
-    loop {
-        let (last_x, last_y, last_dir) = last_state;
-        let new_state = match last_dir {
-            Direction::Up => {
-                if last_y == 0 {
-                    break;
-                } else if map[last_y - 1][last_x] != CellType::Wall {
-                    (last_x, last_y - 1, last_dir)
-                } else {
-                    (last_x, last_y, Direction::Right)
-                }
-            }
-            Direction::Left => {
-                if last_x == 0 {
-                    break;
-                } else if map[last_y][last_x - 1] != CellType::Wall {
-                    (last_x - 1, last_y, last_dir)
-                } else {
-                    (last_x, last_y, Direction::Up)
-                }
-            }
-            Direction::Right => {
-                if last_x + 1 == map[last_y].len() {
-                    break;
-                } else if map[last_y][last_x + 1] != CellType::Wall {
-                    (last_x + 1, last_y, last_dir)
-                } else {
-                    (last_x, last_y, Direction::Down)
-                }
-            }
-            Direction::Down => {
-                if last_y + 1 == map.len() {
-                    break;
-                } else if map[last_y + 1][last_x] != CellType::Wall {
-                    (last_x, last_y + 1, last_dir)
-                } else {
-                    (last_x, last_y, Direction::Left)
-                }
-            }
-        };
+/// Obstacle making the guard walk in a loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoopObstacle {
+    pub position: Point,
+    /// Number of steps of one lap of the loop.
+    pub loop_length: usize,
+}
+
+/// Route walked by the guard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    /// Every state of the guard as `(x, y, direction)`, turning being a step of its own where
+    /// only the direction changes.
+    pub steps: Vec<(i64, i64, Direction)>,
+    /// Whether the guard walks in a loop, in which case `steps` stops right before the first
+    /// repeated state instead of when the guard leaves the map.
+    pub looped: bool,
+}
+
+/// Route of the guard until it leaves the map or starts repeating itself.
+pub fn route(lab: &Lab) -> anyhow::Result<Route> {
+    let (map, start) = (&lab.map, lab.guard()?);
+
+    let states = walk(map, start).collect::<Vec<_>>();
+    let looped = states
+        .last()
+        .is_some_and(|state| step(map, *state).is_some());
+
+    Ok(Route {
+        steps: states.into_iter().map(|(p, dir)| (p.x, p.y, dir)).collect(),
+        looped,
+    })
+}
+
+/// Obstacles making the guard loop, in the order the guard reaches them on its route.
+pub fn loop_obstacles(lab: &Lab) -> anyhow::Result<Vec<LoopObstacle>> {
+    let (map, start) = (&lab.map, lab.guard()?);
+
+    let jumps = Jumps::new(map);
+    let obstacles = get_possible_positions(map, start)
+        .into_par_iter()
+        .filter_map(|position| {
+            let loop_length = jumps.loop_length(start, position)?;
+            Some(LoopObstacle {
+                position,
+                loop_length,
+            })
+        })
+        .collect();
+
+    Ok(obstacles)
+}
 
-        let s = (new_state.0, new_state.1);
-        if !guard_states.contains(&s) {
-            guard_states.push(s);
+/// Map with the route of the guard drawn like in the puzzle, with an extra `obstacle` drawn
+/// as `O`.
+///
+/// Cells walked vertically are drawn as `|`, horizontally as `-` and both ways as `+`. The
+/// route stops when the guard leaves the map or walks in a loop.
+pub fn render(lab: &Lab, obstacle: Option<Point>) -> anyhow::Result<String> {
+    let (mut map, start) = (lab.map.clone(), lab.guard()?);
+    if let Some(obstacle) = obstacle {
+        if map.get(obstacle) != Some(&CellType::Empty) || obstacle == start.0 {
+            anyhow::bail!("cannot place an obstacle on {}", obstacle);
         }
-        last_state = new_state;
+        map[obstacle] = CellType::Wall;
+    }
+
+    // Whether each cell was walked vertically and horizontally.
+    let mut walked = Grid::new(map.width(), map.height(), (false, false));
+    for (position, dir) in walk(&map, start) {
+        match dir {
+            Direction::Up | Direction::Down => walked[position].0 = true,
+            Direction::Left | Direction::Right => walked[position].1 = true,
+        }
+    }
+
+    let mut rendered = Grid::new(map.width(), map.height(), '.');
+    for (p, c) in rendered.iter_mut() {
+        *c = match (map[p], walked[p]) {
+            _ if p == start.0 => direction_char(start.1),
+            _ if Some(p) == obstacle => 'O',
+            (CellType::Wall, _) => '#',
+            (_, (true, true)) => '+',
+            (_, (true, false)) => '|',
+            (_, (false, true)) => '-',
+            _ => '.',
+        };
+    }
+
+    Ok(rendered.to_string())
+}
+
+fn direction_char(dir: Direction) -> char {
+    match dir {
+        Direction::Up => '^',
+        Direction::Right => '>',
+        Direction::Down => 'v',
+        Direction::Left => '<',
     }
-    guard_states
 }
 
 #[cfg(test)]
@@ -310,14 +462,22 @@ mod test {
         io::{BufRead, BufReader},
     };
 
+    use crate::{
+        error::ParseError,
+        grid::{Direction, Point},
+    };
+
+    use super::Edges;
+
     #[test]
     fn part_one() {
         let input_file = BufReader::new(
             File::open("./inputs/day06-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let lab =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let res = super::part_one(&lab, Edges::Exit).expect("failed to run part_one");
 
         assert_eq!(41, res);
     }
@@ -328,9 +488,241 @@ mod test {
             File::open("./inputs/day06-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_two(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let lab =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let res = super::part_two(&lab, Edges::Exit).expect("failed to run part_one");
+
+        assert_eq!(6, res);
+    }
+
+    #[test]
+    fn looping_guard() {
+        // The guard walks in a loop before any obstacle is placed.
+        let lines = [".#..", "...#", "#^..", "..#."].map(String::from);
+        let lab = super::parse(lines.into_iter()).expect("failed to parse input");
+
+        let res = super::part_one(&lab, Edges::Exit).expect("failed to run part_one");
+        assert_eq!(4, res);
+
+        // Every obstacle on the loop opens a way out of the map.
+        let res = super::part_two(&lab, Edges::Exit).expect("failed to run part_two");
+        assert_eq!(0, res);
+    }
+
+    #[test]
+    fn jumps() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day06-test.txt").expect("failed to open input file"),
+        );
+
+        let lab = super::parse(input_file.lines().map(|l| l.unwrap())).unwrap();
+        let start = lab.guard().unwrap();
+        let jumps = super::Jumps::new(&lab.map);
+
+        let mut obstacles = super::get_possible_positions(&lab.map, start)
+            .into_iter()
+            .filter(|obstacle| jumps.loop_length(start, *obstacle).is_some())
+            .map(|p| (p.x, p.y))
+            .collect::<Vec<_>>();
+        obstacles.sort();
+
+        assert_eq!(
+            vec![(1, 8), (3, 6), (3, 8), (6, 7), (7, 7), (7, 9)],
+            obstacles
+        );
+        assert_eq!(
+            Some((Point::new(4, 1), Direction::Right)),
+            jumps.jump(start, Point::new(0, 0))
+        );
+        assert_eq!(
+            Some((Point::new(4, 4), Direction::Right)),
+            jumps.jump(start, Point::new(4, 3))
+        );
+    }
+
+    #[test]
+    fn route() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day06-test.txt").expect("failed to open input file"),
+        );
+
+        let lab =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let route = super::route(&lab).expect("failed to route");
+
+        assert_eq!(
+            vec![
+                (4, 6, Direction::Up),
+                (4, 5, Direction::Up),
+                (4, 4, Direction::Up),
+                (4, 3, Direction::Up),
+                (4, 2, Direction::Up),
+                (4, 1, Direction::Up),
+                (4, 1, Direction::Right),
+                (5, 1, Direction::Right),
+            ],
+            route.steps[..8]
+        );
+        assert_eq!(Some(&(7, 9, Direction::Down)), route.steps.last());
+        assert!(!route.looped);
+
+        let lines = [".#..", "...#", "#^..", "..#."].map(String::from);
+
+        let lab = super::parse(lines.into_iter()).expect("failed to parse input");
+        let route = super::route(&lab).expect("failed to route");
+
+        assert!(route.looped);
+        assert_eq!(8, route.steps.len());
+        assert_eq!(Some(&(1, 2, Direction::Left)), route.steps.last());
+    }
+
+    #[test]
+    fn loop_obstacles() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day06-test.txt").expect("failed to open input file"),
+        );
+
+        let lab =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let obstacles = super::loop_obstacles(&lab).expect("failed to find obstacles");
+
+        assert_eq!(6, obstacles.len());
+        assert_eq!(
+            Some(18),
+            obstacles
+                .iter()
+                .find(|o| o.position == Point::new(3, 6))
+                .map(|o| o.loop_length)
+        );
+
+        let lines = [".#..", "...#", "#^..", "..#."].map(String::from);
+
+        let lab = super::parse(lines.into_iter()).expect("failed to parse input");
+        let obstacles = super::loop_obstacles(&lab).expect("failed to find obstacles");
+
+        assert!(obstacles.is_empty());
+    }
+
+    #[test]
+    fn render() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day06-test.txt").expect("failed to open input file"),
+        );
+
+        let lab =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let res = super::render(&lab, Some(Point::new(3, 6))).expect("failed to render");
+
+        assert_eq!(
+            "....#.....\n\
+             ....+---+#\n\
+             ....|...|.\n\
+             ..#.|...|.\n\
+             ....|..#|.\n\
+             ....|...|.\n\
+             .#.O^---+.\n\
+             ........#.\n\
+             #.........\n\
+             ......#...",
+            res
+        );
+    }
+
+    #[test]
+    fn several_guards() {
+        let lines = [".....", ".>.<.", "....."].map(String::from);
+
+        let lab = super::parse(lines.into_iter()).expect("failed to parse input");
+        let patrol = super::simulate(&lab, Edges::Exit);
+
+        assert!(!patrol.looped);
+        assert_eq!(5, patrol.visited.len());
+
+        // Guards facing each other in a closed room block each other forever.
+        let lines = ["#####", "#>.<#", "#####"].map(String::from);
+
+        let lab = super::parse(lines.into_iter()).expect("failed to parse input");
+        let patrol = super::simulate(&lab, Edges::Exit);
+
+        assert!(patrol.looped);
+        assert_eq!(3, patrol.visited.len());
+    }
+
+    #[test]
+    fn several_guards_part_two() {
+        // A second guard walking off the map right away changes nothing for the first one.
+        let input_file = BufReader::new(
+            File::open("./inputs/day06-test.txt").expect("failed to open input file"),
+        );
+        let lines = input_file
+            .lines()
+            .map(|l| l.unwrap())
+            .enumerate()
+            .map(|(i, l)| if i == 9 { format!("{}v", &l[..9]) } else { l })
+            .collect::<Vec<_>>();
+
+        let lab = super::parse(lines.into_iter()).expect("failed to parse input");
+
+        let res = super::part_one(&lab, Edges::Exit).expect("failed to run part_one");
+        assert_eq!(42, res);
 
+        let res = super::part_two(&lab, Edges::Exit).expect("failed to run part_two");
         assert_eq!(6, res);
     }
+
+    #[test]
+    fn wrapping_edges() {
+        let lines = ["...", ".^.", "..."].map(String::from);
+
+        let lab = super::parse(lines.into_iter()).expect("failed to parse input");
+        let patrol = super::simulate(&lab, Edges::Wrap);
+
+        assert!(patrol.looped);
+        assert_eq!(3, patrol.visited.len());
+
+        let input_file = BufReader::new(
+            File::open("./inputs/day06-test.txt").expect("failed to open input file"),
+        );
+        let lab =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let patrol = super::simulate(&lab, Edges::Wrap);
+
+        assert!(patrol.looped);
+
+        // A guard wrapping around a one-cell wide map comes back on its own cell.
+        let lines = ["#", ">", "."].map(String::from);
+
+        let lab = super::parse(lines.into_iter()).expect("failed to parse input");
+        let patrol = super::simulate(&lab, Edges::Wrap);
+
+        assert!(patrol.looped);
+        assert_eq!(1, patrol.visited.len());
+    }
+
+    #[test]
+    fn wrapping_edges_part_two() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day06-test.txt").expect("failed to open input file"),
+        );
+        let lab =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+
+        // The guard never leaves a wrapping map, so any obstacle on its route keeps it looping.
+        let visited = super::part_one(&lab, Edges::Wrap).expect("failed to run part_one");
+        let res = super::part_two(&lab, Edges::Wrap).expect("failed to run part_two");
+
+        assert_eq!(visited - 1, res);
+    }
+
+    #[test]
+    fn invalid_cell() {
+        let lines = ["..#", ".^.", "#x."].map(String::from);
+
+        let err = super::extract_map(lines.into_iter()).expect_err("input should be invalid");
+        let err = err
+            .downcast_ref::<ParseError>()
+            .expect("error should be a ParseError");
+
+        assert_eq!((3, 2, "x"), (err.line, err.column, err.text.as_str()));
+    }
 }
@@ -1,31 +1,61 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use crate::{error::ParseError, solution::Solution};
 
-pub fn run_day() -> anyhow::Result<()> {
-    println!("=== DAY 07 ===");
+pub struct Day07;
 
-    let res_part_one = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day07.txt").expect("failed to open input file"));
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Bridge Repair";
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_one(lines)?
-    };
+    type Input = Vec<(i64, Vec<i64>)>;
+    type PartOne = i64;
+    type PartTwo = i64;
 
-    println!("part one : {}", res_part_one);
+    fn parse<I>(lines: I) -> anyhow::Result<Vec<(i64, Vec<i64>)>>
+    where
+        I: Iterator<Item = String>,
+    {
+        Ok(parse_equations(lines)?)
+    }
 
-    let res_part_two = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day07.txt").expect("failed to open input file"));
+    fn part_one(equations: &Vec<(i64, Vec<i64>)>) -> anyhow::Result<i64> {
+        part_one(equations)
+    }
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_two(lines)?
-    };
+    fn part_two(equations: &Vec<(i64, Vec<i64>)>) -> anyhow::Result<i64> {
+        part_two(equations)
+    }
+}
 
-    println!("part two : {}", res_part_two);
-    Ok(())
+fn parse_number(index: usize, offset: usize, text: &str) -> Result<i64, ParseError> {
+    text.parse::<i64>()
+        .map_err(|_| ParseError::new(Day07::DAY, index, offset, text, "a number"))
+}
+
+fn parse_equations<I>(lines: I) -> Result<Vec<(i64, Vec<i64>)>, ParseError>
+where
+    I: Iterator<Item = String>,
+{
+    lines
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| {
+            let (res, values) = line
+                .split_once(": ")
+                .ok_or_else(|| ParseError::new(Day07::DAY, index, 0, line.as_str(), "`: `"))?;
+
+            let mut offset = res.len() + 2;
+            let values = values
+                .split(' ')
+                .map(|value| {
+                    let value_offset = offset;
+                    offset += value.len() + 1;
+                    parse_number(index, value_offset, value)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok((parse_number(index, 0, res)?, values))
+        })
+        .collect()
 }
 
 fn is_possible(res: i64, current: i64, values: &[i64]) -> bool {
@@ -37,22 +67,11 @@ fn is_possible(res: i64, current: i64, values: &[i64]) -> bool {
         || is_possible(res, current * values[0], &values[1..])
 }
 
-fn part_one<I>(lines: I) -> anyhow::Result<i64>
-where
-    I: Iterator<Item = String>,
-{
-    let res = lines
-        .map(|line| {
-            let (res, line) = line.split_once(": ").unwrap();
-            (
-                res.parse::<i64>().unwrap(),
-                line.split(' ')
-                    .map(|c| c.parse::<i64>().unwrap())
-                    .collect::<Vec<_>>(),
-            )
-        })
+fn part_one(equations: &[(i64, Vec<i64>)]) -> anyhow::Result<i64> {
+    let res = equations
+        .iter()
         .filter_map(|(res, eq)| {
-            if is_possible(res, eq[0], &eq[1..]) {
+            if is_possible(*res, eq[0], &eq[1..]) {
                 Some(res)
             } else {
                 None
@@ -76,22 +95,11 @@ fn is_possible2(res: i64, current: i64, values: &[i64]) -> bool {
         }
 }
 
-fn part_two<I>(lines: I) -> anyhow::Result<i64>
-where
-    I: Iterator<Item = String>,
-{
-    let res = lines
-        .map(|line| {
-            let (res, line) = line.split_once(": ").unwrap();
-            (
-                res.parse::<i64>().unwrap(),
-                line.split(' ')
-                    .map(|c| c.parse::<i64>().unwrap())
-                    .collect::<Vec<_>>(),
-            )
-        })
+fn part_two(equations: &[(i64, Vec<i64>)]) -> anyhow::Result<i64> {
+    let res = equations
+        .iter()
         .filter_map(|(res, eq)| {
-            if is_possible2(res, eq[0], &eq[1..]) {
+            if is_possible2(*res, eq[0], &eq[1..]) {
                 Some(res)
             } else {
                 None
@@ -114,8 +122,9 @@ mod test {
             File::open("./inputs/day07-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input = super::parse_equations(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
 
         assert_eq!(3749, res);
     }
@@ -126,9 +135,19 @@ mod test {
             File::open("./inputs/day07-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_two(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input = super::parse_equations(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_one");
 
         assert_eq!(11387, res);
     }
+
+    #[test]
+    fn invalid_number() {
+        let lines = ["190: 10 19", "3267: 81 4O 27"].map(String::from);
+
+        let err = super::parse_equations(lines.into_iter()).expect_err("input should be invalid");
+
+        assert_eq!((2, 10, "4O"), (err.line, err.column, err.text.as_str()));
+    }
 }
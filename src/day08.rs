@@ -1,168 +1,85 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::collections::HashMap;
 
-pub fn run_day() -> anyhow::Result<()> {
-    println!("=== DAY 08 ===");
+use crate::{
+    grid::{Grid, Point},
+    solution::Solution,
+};
 
-    let res_part_one = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day08.txt").expect("failed to open input file"));
+pub struct Day08;
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_one(lines)?
-    };
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Resonant Collinearity";
 
-    println!("part one : {}", res_part_one);
+    type Input = Grid<char>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-    let res_part_two = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day08.txt").expect("failed to open input file"));
+    fn parse<I>(lines: I) -> anyhow::Result<Grid<char>>
+    where
+        I: Iterator<Item = String>,
+    {
+        Grid::from_lines(lines)
+    }
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_two(lines)?
-    };
+    fn part_one(city_map: &Grid<char>) -> anyhow::Result<usize> {
+        part_one(city_map)
+    }
 
-    println!("part two : {}", res_part_two);
-    Ok(())
+    fn part_two(city_map: &Grid<char>) -> anyhow::Result<usize> {
+        part_two(city_map)
+    }
 }
 
-fn get_city_map<I>(lines: I) -> (Vec<Vec<char>>, Vec<Vec<usize>>)
-where
-    I: Iterator<Item = String>,
-{
-    let lines: Vec<Vec<char>> = lines.map(|l| l.chars().collect()).collect();
-    let antipodes = lines
-        .iter()
-        .map(|c| c.iter().map(|_| 0).collect())
-        .collect();
-    (lines, antipodes)
-}
+/// Every ordered pair of distinct antennas sharing the same frequency.
+fn antenna_pairs(city_map: &Grid<char>) -> impl Iterator<Item = (Point, Point)> {
+    let mut antennas: HashMap<char, Vec<Point>> = HashMap::new();
+    for (p, s) in city_map.iter() {
+        if *s != '.' {
+            antennas.entry(*s).or_default().push(p);
+        }
+    }
 
-fn iter_other_antennas<'s, I>(
-    city_map: I,
-    x: usize,
-    y: usize,
-    s: char,
-) -> impl Iterator<Item = (i64, i64)> + use<'s, I>
-where
-    I: Iterator<Item = &'s [char]>,
-{
-    city_map
-        .enumerate()
-        .filter(move |(oy, _)| *oy != y)
-        .flat_map(move |(oy, row)| {
-            row.iter().enumerate().find_map(|(ox, s2)| {
-                if *s2 == s && ox != x && oy != y {
-                    Some((ox as i64, oy as i64))
-                } else {
-                    None
-                }
+    antennas.into_values().flat_map(|points| {
+        points
+            .iter()
+            .flat_map(|a| {
+                points
+                    .iter()
+                    .filter(move |b| a != *b)
+                    .map(move |b| (*a, *b))
             })
-        })
+            .collect::<Vec<_>>()
+    })
 }
 
-#[inline]
-fn update_if_needed(antipodes: &mut [Vec<usize>], ny: i64, nx: i64) {
-    if ny >= 0 && (ny as usize) < antipodes.len() && nx >= 0 && (nx as usize) < antipodes[ny as usize].len() {
-        antipodes[ny as usize][nx as usize] += 1;
-    }
-}
+fn part_one(city_map: &Grid<char>) -> anyhow::Result<usize> {
+    let mut antipodes = city_map.map(|_| false);
 
-fn part_one<I>(lines: I) -> anyhow::Result<usize>
-where
-    I: Iterator<Item = String>,
-{
-    let (city_map, mut antipodes) = get_city_map(lines);
-
-    for y in 0..city_map.len() {
-        for x in 0..city_map[y].len() {
-            let s = city_map[y][x];
-            if s != '.' {
-                for (ox, oy) in iter_other_antennas(city_map.iter().map(|r| r.as_slice()), x, y, s)
-                {
-                    let y = y as i64;
-                    let x = x as i64;
-
-                    let delta_x = ox - x;
-                    let delta_y = oy - y;
-
-                    let ny = y - delta_y;
-                    let nx = x - delta_x;
-                    update_if_needed(&mut antipodes, ny, nx);
-
-                    let ny = oy + delta_y;
-                    let nx = ox + delta_x;
-                    update_if_needed(&mut antipodes, ny, nx);
-                }
+    for (a, b) in antenna_pairs(city_map) {
+        let delta = b - a;
+        for p in [a - delta, b + delta] {
+            if let Some(antipode) = antipodes.get_mut(p) {
+                *antipode = true;
             }
         }
     }
 
-    let res = antipodes
-        .iter()
-        .flat_map(|row| row.iter().filter(|n| **n > 0))
-        .count();
-
-    Ok(res)
+    Ok(antipodes.values().filter(|a| **a).count())
 }
 
-fn part_two<I>(lines: I) -> anyhow::Result<usize>
-where
-    I: Iterator<Item = String>,
-{
-    let (city_map, mut antipodes) = get_city_map(lines);
-
-    for y in 0..city_map.len() {
-        for x in 0..city_map[y].len() {
-            let s = city_map[y][x];
-            if s != '.' {
-                for (ox, oy) in iter_other_antennas(city_map.iter().map(|r| r.as_slice()), x, y, s)
-                {
-                    let y = y as i64;
-                    let x = x as i64;
-
-                    antipodes[oy as usize][ox as usize] += 1;
-
-                    let delta_x = ox - x;
-                    let delta_y = oy - y;
-
-                    let mut ny = y - delta_y;
-                    let mut nx = x - delta_x;
-
-                    while ny >= 0
-                        && (ny as usize) < antipodes.len()
-                        && nx >= 0
-                        && (nx as usize) < antipodes[ny as usize].len()
-                    {
-                        antipodes[ny as usize][nx as usize] += 1;
-                        ny -= delta_y;
-                        nx -= delta_x;
-                    }
-
-                    ny = oy + delta_y;
-                    nx = ox + delta_x;
-                    while ny >= 0
-                        && (ny as usize) < antipodes.len()
-                        && nx >= 0
-                        && (nx as usize) < antipodes[ny as usize].len()
-                    {
-                        antipodes[ny as usize][nx as usize] += 1;
-                        ny += delta_y;
-                        nx += delta_x;
-                    }
-                }
-            }
+fn part_two(city_map: &Grid<char>) -> anyhow::Result<usize> {
+    let mut antipodes = city_map.map(|_| false);
+
+    for (a, b) in antenna_pairs(city_map) {
+        let delta = b - a;
+        antipodes[b] = true;
+        for p in city_map.ray(b, delta).chain(city_map.ray(a, -delta)) {
+            antipodes[p] = true;
         }
     }
 
-    let res = antipodes
-        .iter()
-        .flat_map(|row| row.iter().filter(|n| **n > 0))
-        .count();
-
-    Ok(res)
+    Ok(antipodes.values().filter(|a| **a).count())
 }
 
 #[cfg(test)]
@@ -178,8 +95,9 @@ mod test {
             File::open("./inputs/day08-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input = super::Grid::from_lines(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
 
         assert_eq!(14, res);
     }
@@ -190,8 +108,9 @@ mod test {
             File::open("./inputs/day08-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_two(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input = super::Grid::from_lines(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_one");
 
         assert_eq!(34, res);
     }
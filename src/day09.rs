@@ -1,90 +1,176 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::{cmp::Reverse, collections::BinaryHeap};
 
-pub fn run_day() -> anyhow::Result<()> {
-    println!("=== DAY 09 ===");
+use crate::{error::ParseError, solution::Solution};
 
-    let res_part_one = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day09.txt").expect("failed to open input file"));
+pub struct Day09;
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_one(lines)?
-    };
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Disk Fragmenter";
 
-    println!("part one : {}", res_part_one);
+    type Input = DiskMap;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-    let res_part_two = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day09.txt").expect("failed to open input file"));
+    fn parse<I>(lines: I) -> anyhow::Result<DiskMap>
+    where
+        I: Iterator<Item = String>,
+    {
+        parse_disk_map(lines)
+    }
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_two(lines)?
-    };
+    fn part_one(disk: &DiskMap) -> anyhow::Result<usize> {
+        part_one(disk)
+    }
 
-    println!("part two : {}", res_part_two);
-    Ok(())
+    fn part_two(disk: &DiskMap) -> anyhow::Result<usize> {
+        part_two(disk)
+    }
 }
 
-fn is_sorted(l: &[Option<usize>]) -> bool {
-    let mut iter = l.iter();
-    for s in iter.by_ref() {
-        if s.is_none() {
-            break;
-        }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Span {
+    offset: usize,
+    size: usize,
+}
+
+/// Files and free spans of the disk, files being indexed by their id.
+#[derive(Debug, Clone)]
+pub struct DiskMap {
+    files: Vec<Span>,
+    free: Vec<Span>,
+}
+
+impl DiskMap {
+    fn len(&self) -> usize {
+        self.files
+            .iter()
+            .chain(&self.free)
+            .map(|s| s.offset + s.size)
+            .max()
+            .unwrap_or(0)
     }
 
-    for s in iter {
-        if s.is_some() {
-            return false;
+    /// Content of every block, `None` for free blocks.
+    fn blocks(&self) -> Vec<Option<usize>> {
+        let mut blocks = vec![None; self.len()];
+        for (id, file) in self.files.iter().enumerate() {
+            blocks[file.offset..file.offset + file.size].fill(Some(id));
         }
+        blocks
     }
 
-    true
+    fn checksum(&self) -> usize {
+        self.files
+            .iter()
+            .enumerate()
+            .map(|(id, f)| id * (f.offset * f.size + f.size * f.size.saturating_sub(1) / 2))
+            .sum()
+    }
 }
 
-fn part_one<I>(mut lines: I) -> anyhow::Result<usize>
+/// Disk map written on the first line of the puzzle input.
+pub fn parse_disk_map<I>(mut lines: I) -> anyhow::Result<DiskMap>
 where
     I: Iterator<Item = String>,
 {
-    let input = lines.next().unwrap();
-
-    let mut a = input
-        .chars()
-        .scan((true, 0), |(s, n), c| {
-            let state = *s;
-            *s = !state;
-            let size = c.to_digit(10).unwrap() as usize;
-            let res: Vec<_> = if state {
-                let nbr = *n;
-                if state {
-                    *n += 1;
-                }
-                (0..size).map(|_| Some(nbr)).collect()
-            } else {
-                (0..size).map(|_| None).collect()
-            };
-            Some(res)
-        })
-        .flatten()
-        .collect::<Vec<_>>();
-
-    let mut index = a.len() - 1;
-    let mut last_edited = 0;
-    while !is_sorted(&a) && index > last_edited {
-        let item = a[index].take();
-        if let Some(item) = item {
-            let first_index =
-                a[last_edited..].iter().position(|&x| x.is_none()).unwrap() + last_edited;
-            a[first_index] = Some(item);
-            last_edited = first_index;
+    let line = lines
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("no disk map in the input"))?;
+
+    let mut disk = DiskMap {
+        files: Vec::new(),
+        free: Vec::new(),
+    };
+
+    let mut offset = 0;
+    for (column, c) in line.chars().enumerate() {
+        let size = c
+            .to_digit(10)
+            .ok_or_else(|| ParseError::new(Day09::DAY, 0, column, c, "a digit"))?
+            as usize;
+
+        let span = Span { offset, size };
+        if column % 2 == 0 {
+            disk.files.push(span);
+        } else {
+            disk.free.push(span);
         }
-        index -= 1;
+        offset += size;
     }
 
-    let res = a
+    Ok(disk)
+}
+
+/// File, or block of a file, moved while compacting the disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub id: usize,
+    /// Offset of the first moved block before the move.
+    pub from: usize,
+    /// Offset of the first moved block after the move.
+    pub to: usize,
+}
+
+/// Move the last file block to the first free block until they cross.
+fn compact_blocks<F>(blocks: &mut [Option<usize>], mut on_move: F)
+where
+    F: FnMut(Move),
+{
+    let (mut free, mut last) = (0, blocks.len());
+    loop {
+        while free < blocks.len() && blocks[free].is_some() {
+            free += 1;
+        }
+        while last > 0 && blocks[last - 1].is_none() {
+            last -= 1;
+        }
+        if last == 0 || free >= last - 1 {
+            break;
+        }
+
+        blocks.swap(free, last - 1);
+        if let Some(id) = blocks[free] {
+            on_move(Move {
+                id,
+                from: last - 1,
+                to: free,
+            });
+        }
+    }
+}
+
+/// Move every file, from the highest id, to the leftmost free span large enough for it.
+fn compact_files<F>(disk: &mut DiskMap, mut on_move: F)
+where
+    F: FnMut(Move),
+{
+    let mut free = FreeSpans::new(&disk.free);
+
+    // A moved file frees space to the right of every file left to move, where nothing will
+    // ever move, so it is not given back.
+    for (id, file) in disk.files.iter_mut().enumerate().rev() {
+        // An empty file has no block to move.
+        if file.size == 0 {
+            continue;
+        }
+
+        if let Some(span) = free.take(file.size, file.offset) {
+            on_move(Move {
+                id,
+                from: file.offset,
+                to: span.offset,
+            });
+            file.offset = span.offset;
+        }
+    }
+}
+
+fn part_one(disk: &DiskMap) -> anyhow::Result<usize> {
+    let mut blocks = disk.blocks();
+    compact_blocks(&mut blocks, |_| ());
+
+    let res = blocks
         .iter()
         .enumerate()
         .filter_map(|(i, v)| v.map(|v| i * v))
@@ -92,110 +178,124 @@ where
     Ok(res)
 }
 
-#[derive(Debug)]
-enum Segment {
-    Empty(usize),
-    File { id: usize, size: usize },
+/// Free spans indexed by their size, each in a min-heap on their offset.
+///
+/// The leftmost span fitting a file is the leftmost of the heads of every heap of a large
+/// enough size, so finding it takes a look at a handful of heaps instead of a scan of the disk.
+struct FreeSpans {
+    by_size: [BinaryHeap<Reverse<usize>>; 10],
 }
 
-impl Segment {
-    fn size(&self) -> usize {
-        match self {
-            Segment::Empty(u) => *u,
-            Self::File { size, .. } => *size,
+impl FreeSpans {
+    fn new(spans: &[Span]) -> Self {
+        let mut by_size: [BinaryHeap<Reverse<usize>>; 10] = Default::default();
+        for span in spans.iter().filter(|s| s.size > 0) {
+            by_size[span.size].push(Reverse(span.offset));
+        }
+        Self { by_size }
+    }
+
+    /// Remove the leftmost span of at least `size` blocks starting before `before`, giving
+    /// back what is left of it.
+    fn take(&mut self, size: usize, before: usize) -> Option<Span> {
+        let (span_size, offset) = (size..self.by_size.len())
+            .filter_map(|s| self.by_size[s].peek().map(|Reverse(offset)| (s, *offset)))
+            .min_by_key(|(_, offset)| *offset)?;
+        if offset >= before {
+            return None;
+        }
+
+        self.by_size[span_size].pop();
+        if span_size > size {
+            self.by_size[span_size - size].push(Reverse(offset + size));
         }
+
+        Some(Span {
+            offset,
+            size: span_size,
+        })
     }
 }
 
-fn part_two<I>(mut lines: I) -> anyhow::Result<usize>
+fn part_two(disk: &DiskMap) -> anyhow::Result<usize> {
+    let mut disk = disk.clone();
+    compact_files(&mut disk, |_| ());
+
+    Ok(disk.checksum())
+}
+
+/// How the disk is compacted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Block by block, as in part one.
+    Blocks,
+    /// Whole files at once, as in part two.
+    Files,
+}
+
+/// Step of a traced compaction, reported as soon as it happens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceStep<'a> {
+    Move(Move),
+    /// Layout of the whole disk at this point.
+    Layout(&'a str),
+}
+
+/// Compact the disk with `strategy`, reporting every move to `on_step` along with the layout
+/// of the disk before any move, then after every `every` moves and once compacted.
+///
+/// Layouts are drawn like in the puzzle, free blocks as `.` and file blocks as their id, ids
+/// above 9 being written in base 36 and wrapping around after `z`.
+pub fn trace<F>(
+    disk: &DiskMap,
+    strategy: Strategy,
+    every: usize,
+    mut on_step: F,
+) -> anyhow::Result<()>
 where
-    I: Iterator<Item = String>,
+    F: FnMut(TraceStep),
 {
-    let input = lines.next().unwrap();
-    let mut a = input
-        .chars()
-        .scan((true, 0), |(s, n), c| {
-            let state = *s;
-            *s = !state;
-            let size = c.to_digit(10).unwrap() as usize;
-            let res = if state {
-                let nbr = *n;
-                if state {
-                    *n += 1;
-                }
-                Segment::File { id: nbr, size }
-            } else {
-                Segment::Empty(size)
-            };
-            Some(res)
-        })
-        .collect::<Vec<_>>();
-
-    let max_id = a
-        .iter()
-        .filter_map(|f| {
-            if let Segment::File { id, .. } = f {
-                Some(*id)
-            } else {
-                None
-            }
-        })
-        .max()
-        .unwrap();
+    if every == 0 {
+        anyhow::bail!("the layout must be drawn every 1 move or more");
+    }
 
-    let mut current_id = max_id + 1;
-    while current_id > 0 {
-        current_id -= 1;
-        let (pos, needed_size) = a
-            .iter()
-            .enumerate()
-            .find_map(|(i, s)| {
-                if let Segment::File { id, size } = s {
-                    if *id == current_id {
-                        Some((i, *size))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            })
-            .unwrap();
-
-        let to_change = a[0..pos].iter().position(|s| {
-            if let Segment::Empty(u) = s {
-                *u >= needed_size
-            } else {
-                false
-            }
-        });
-
-        if let Some(to_change) = to_change {
-            let s = std::mem::replace(&mut a[pos], Segment::Empty(needed_size));
-            let empty_space = a[to_change].size();
-            a[to_change] = s;
-            if empty_space - needed_size > 0 {
-                a.insert(to_change + 1, Segment::Empty(empty_space - needed_size));
-            }
-        }
+    let mut moves = Vec::new();
+    match strategy {
+        Strategy::Blocks => compact_blocks(&mut disk.blocks(), |m| moves.push(m)),
+        Strategy::Files => compact_files(&mut disk.clone(), |m| moves.push(m)),
     }
 
-    let res = a.into_iter().scan(0, |state, s| match s {
-        Segment::Empty(u) => {
-            *state += u;
-            Some(0)
+    // Replay the moves on the initial layout, drawing it in the same buffer every time.
+    let mut blocks = disk.blocks();
+    let mut drawn = String::with_capacity(blocks.len());
+    layout(&blocks, &mut drawn);
+    on_step(TraceStep::Layout(&drawn));
+
+    for (i, m) in moves.iter().enumerate() {
+        let size = match strategy {
+            Strategy::Blocks => 1,
+            Strategy::Files => disk.files[m.id].size,
+        };
+        for k in 0..size {
+            blocks[m.to + k] = blocks[m.from + k].take();
         }
-        Segment::File { id, size } => {
-            let mut r = 0;
-            for i in *state..*state + size {
-                r += i * id;
-            }
-            *state += size;
-            Some(r)
+        on_step(TraceStep::Move(*m));
+
+        if (i + 1) % every == 0 || i + 1 == moves.len() {
+            layout(&blocks, &mut drawn);
+            on_step(TraceStep::Layout(&drawn));
         }
-    }).sum();
+    }
 
-    Ok(res)
+    Ok(())
+}
+
+fn layout(blocks: &[Option<usize>], drawn: &mut String) {
+    drawn.clear();
+    drawn.extend(blocks.iter().map(|b| match b {
+        Some(id) => char::from_digit((id % 36) as u32, 36).unwrap(),
+        None => '.',
+    }));
 }
 
 #[cfg(test)]
@@ -205,14 +305,17 @@ mod test {
         io::{BufRead, BufReader},
     };
 
+    use super::{Strategy, TraceStep};
+
     #[test]
     fn part_one() {
         let input_file = BufReader::new(
             File::open("./inputs/day09-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input = super::parse_disk_map(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
 
         assert_eq!(1928, res);
     }
@@ -223,9 +326,84 @@ mod test {
             File::open("./inputs/day09-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_two(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_two");
+        let input = super::parse_disk_map(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_two");
 
         assert_eq!(2858, res);
     }
+
+    #[test]
+    fn small_disk() {
+        let disk = super::parse_disk_map(std::iter::once("12345".to_string()))
+            .expect("failed to parse input");
+
+        assert_eq!(60, super::part_one(&disk).expect("failed to run part_one"));
+        assert_eq!(132, super::part_two(&disk).expect("failed to run part_two"));
+        assert!(super::parse_disk_map(std::iter::once("12x".to_string())).is_err());
+    }
+
+    /// Steps of a trace, moves written as `id: from -> to`.
+    fn trace<I>(lines: I, strategy: Strategy, every: usize) -> Vec<String>
+    where
+        I: Iterator<Item = String>,
+    {
+        let disk = super::parse_disk_map(lines).expect("failed to parse input");
+        let mut steps = Vec::new();
+        super::trace(&disk, strategy, every, |step| {
+            steps.push(match step {
+                TraceStep::Move(m) => format!("{}: {} -> {}", m.id, m.from, m.to),
+                TraceStep::Layout(layout) => layout.to_string(),
+            })
+        })
+        .expect("failed to trace");
+        steps
+    }
+
+    #[test]
+    fn trace_blocks() {
+        let steps = trace(std::iter::once("12345".to_string()), Strategy::Blocks, 2);
+
+        assert_eq!(
+            vec![
+                "0..111....22222",
+                "2: 14 -> 1",
+                "2: 13 -> 2",
+                "022111....222..",
+                "2: 12 -> 6",
+                "2: 11 -> 7",
+                "02211122..2....",
+                "2: 10 -> 8",
+                "022111222......",
+            ],
+            steps
+        );
+    }
+
+    #[test]
+    fn trace_files() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day09-test.txt").expect("failed to open input file"),
+        );
+
+        let steps = trace(input_file.lines().map(|l| l.unwrap()), Strategy::Files, 2);
+
+        assert_eq!(
+            vec![
+                "00...111...2...333.44.5555.6666.777.888899",
+                "9: 40 -> 2",
+                "7: 32 -> 8",
+                "0099.1117772...333.44.5555.6666.....8888..",
+                "4: 19 -> 12",
+                "2: 11 -> 4",
+                "00992111777.44.333....5555.6666.....8888..",
+            ],
+            steps
+        );
+
+        // The empty file 1 is not moved.
+        let steps = trace(["12011".to_string()].into_iter(), Strategy::Files, 1);
+
+        assert_eq!(vec!["0...2", "2: 4 -> 1", "02..."], steps);
+    }
 }
@@ -1,122 +1,99 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
 use std::collections::HashSet;
 
-pub fn run_day() -> anyhow::Result<()> {
-    println!("=== DAY 10 ===");
+use crate::{
+    error::ParseError,
+    grid::{Grid, Point},
+    solution::Solution,
+};
 
-    let res_part_one = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day10.txt").expect("failed to open input file"));
+pub struct Day10;
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_one(lines)?
-    };
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Hoof It";
 
-    println!("part one : {}", res_part_one);
+    type Input = Grid<u8>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-    let res_part_two = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day10.txt").expect("failed to open input file"));
+    fn parse<I>(lines: I) -> anyhow::Result<Grid<u8>>
+    where
+        I: Iterator<Item = String>,
+    {
+        parse_input(lines)
+    }
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_two(lines)?
-    };
+    fn part_one(grid: &Grid<u8>) -> anyhow::Result<usize> {
+        part_one(grid)
+    }
 
-    println!("part two : {}", res_part_two);
-    Ok(())
+    fn part_two(grid: &Grid<u8>) -> anyhow::Result<usize> {
+        part_two(grid)
+    }
 }
 
-fn parse_input<I>(lines: I) -> Vec<Vec<u8>>
+/// Parse the height map, impassable tiles (`.`) having a height of 10 so that no trail goes
+/// through them.
+fn parse_input<I>(lines: I) -> anyhow::Result<Grid<u8>>
 where
     I: Iterator<Item = String>,
 {
-    lines
-        .map(|l| l.chars().map(|c| c.to_digit(10).unwrap() as u8).collect())
-        .collect()
+    let grid = Grid::from_lines(lines)?.try_map(|p, c| match c {
+        '.' => Ok(10),
+        _ => c.to_digit(10).map(|d| d as u8).ok_or_else(|| {
+            ParseError::new(Day10::DAY, p.y as usize, p.x as usize, *c, "a digit or `.`")
+        }),
+    })?;
+    Ok(grid)
 }
 
-#[inline]
-fn get_grid_value(grid : &[Vec<u8>], x : i64, y : i64) -> u8 {
-    if x == -1 || y == -1 {
-        10
-    } else {
-        *grid.get(y as usize).and_then(|row| row.get(x as usize)).unwrap_or(&10)
-    }
+/// Uphill neighbours of `point`.
+fn next_steps(grid: &Grid<u8>, point: Point) -> impl Iterator<Item = Point> + '_ {
+    let current = grid[point];
+    grid.neighbours(point)
+        .filter(move |p| grid[*p] == current + 1)
 }
 
-fn get_trail_score(grid: &[Vec<u8>], x: usize, y: usize, reachable : &mut  HashSet<(usize, usize)>) {
-    let current = grid[y][x];
-    if current != 9 {
-        let x = x as i64;
-        let y = y as i64;
-        for (x, y) in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
-            let value = get_grid_value(grid, x, y);
-            if value == current + 1 {
-                 get_trail_score(grid, x as usize, y as usize, reachable);
-            }
+fn get_trail_score(grid: &Grid<u8>, point: Point, reachable: &mut HashSet<Point>) {
+    if grid[point] != 9 {
+        for next in next_steps(grid, point) {
+            get_trail_score(grid, next, reachable);
         }
-    } else  {
-        reachable.insert((x, y));
+    } else {
+        reachable.insert(point);
     }
 }
 
-fn part_one<I>(lines: I) -> anyhow::Result<usize>
-where
-    I: Iterator<Item = String>,
-{
-    let input = parse_input(lines);
-
+fn part_one(input: &Grid<u8>) -> anyhow::Result<usize> {
     let mut res = 0;
 
-    for y in 0..input.len() {
-        for x in 0..input[y].len() {
-            if input[y][x] == 0 {
-                let mut reachable = HashSet::new();
-                get_trail_score(&input, x, y, &mut reachable);
+    for (point, height) in input.iter() {
+        if *height == 0 {
+            let mut reachable = HashSet::new();
+            get_trail_score(input, point, &mut reachable);
             res += reachable.len();
-            }
         }
     }
 
     Ok(res)
 }
 
-fn get_trail_again(grid: &[Vec<u8>], x: usize, y: usize) -> usize {
-    let current = grid[y][x];
-    if current != 9 {
-        let x = x as i64;
-        let y = y as i64;
-        let mut res = 0;
-        for (x, y) in [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)] {
-            let value = get_grid_value(grid, x, y);
-            if value == current + 1 {
-                res += get_trail_again(grid, x as usize, y as usize);
-            }
-        }
-        res
-    } else  {
+fn get_trail_again(grid: &Grid<u8>, point: Point) -> usize {
+    if grid[point] != 9 {
+        next_steps(grid, point)
+            .map(|next| get_trail_again(grid, next))
+            .sum()
+    } else {
         1
     }
 }
 
-fn part_two<I>(lines: I) -> anyhow::Result<usize>
-where
-    I: Iterator<Item = String>,
-{
-    let input = parse_input(lines);
-
-    let mut res = 0;
-
-    for y in 0..input.len() {
-        for x in 0..input[y].len() {
-            if input[y][x] == 0 {
-                res += get_trail_again(&input, x, y);
-            }
-        }
-    }
+fn part_two(input: &Grid<u8>) -> anyhow::Result<usize> {
+    let res = input
+        .iter()
+        .filter(|(_, height)| **height == 0)
+        .map(|(point, _)| get_trail_again(input, point))
+        .sum();
 
     Ok(res)
 }
@@ -134,21 +111,22 @@ mod test {
             File::open("./inputs/day10-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input = super::parse_input(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
 
         assert_eq!(36, res);
     }
 
-
     #[test]
     fn part_one_simple() {
         let input_file = BufReader::new(
             File::open("./inputs/day10-test2.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input = super::parse_input(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
 
         assert_eq!(2, res);
     }
@@ -159,8 +137,9 @@ mod test {
             File::open("./inputs/day10-test3.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input = super::parse_input(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
 
         assert_eq!(4, res);
     }
@@ -171,8 +150,9 @@ mod test {
             File::open("./inputs/day10-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_two(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_two");
+        let input = super::parse_input(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_two");
 
         assert_eq!(81, res);
     }
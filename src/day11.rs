@@ -1,62 +1,76 @@
 use std::collections::HashMap;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
 
-pub fn run_day() -> anyhow::Result<()> {
-    println!("=== DAY 11 ===");
+use crate::{error::ParseError, solution::Solution};
 
-    let res_part_one = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day11.txt").expect("failed to open input file"));
+pub struct Day11;
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part::<25, _>(lines)?
-    };
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Plutonian Pebbles";
 
-    println!("part one : {}", res_part_one);
+    type Input = Vec<u64>;
+    type PartOne = u64;
+    type PartTwo = u64;
 
-    let res_part_two = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day11.txt").expect("failed to open input file"));
+    fn parse<I>(mut lines: I) -> anyhow::Result<Vec<u64>>
+    where
+        I: Iterator<Item = String>,
+    {
+        Ok(parse_stones(lines.next().unwrap_or_default())?)
+    }
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part::<75, _>(lines)?
-    };
+    fn part_one(stones: &Vec<u64>) -> anyhow::Result<u64> {
+        part::<25>(stones)
+    }
 
-    println!("part two : {}", res_part_two);
-    Ok(())
+    fn part_two(stones: &Vec<u64>) -> anyhow::Result<u64> {
+        part::<75>(stones)
+    }
 }
 
-fn part<const N: usize, I>(mut lines: I) -> anyhow::Result<u64>
-where
-    I: Iterator<Item = String>,
-{
+fn parse_stones(line: String) -> Result<Vec<u64>, ParseError> {
+    let mut offset = 0;
+    line.split(' ')
+        .map(|stone| {
+            let stone_offset = offset;
+            offset += stone.len() + 1;
+            stone
+                .parse::<u64>()
+                .map_err(|_| ParseError::new(Day11::DAY, 0, stone_offset, stone, "a number"))
+        })
+        .collect()
+}
+
+/// Number of stones after `N` blinks.
+pub fn part<const N: usize>(stones: &[u64]) -> anyhow::Result<u64> {
     let mut cache = HashMap::new();
 
-    let size = lines
-        .next()
-        .unwrap()
-        .split(' ')
-        .map(|n| n.parse::<u64>().unwrap())
-        .map(|c| expand::<N>(0, c, &mut cache))
+    let size = stones
+        .iter()
+        .map(|c| expand::<N>(0, *c, &mut cache))
         .sum::<u64>();
 
     Ok(size)
 }
 
-fn expand<const N: usize>(gen: usize, stone: u64, cache: &mut HashMap<(usize, u64), u64>) -> u64 {
+/// Number of stones `stone` turns into once blinked from generation `gen` up to `N`.
+///
+/// Results are memoised in `cache`, keyed on `(gen, stone)`.
+pub fn expand<const N: usize>(
+    gen: usize,
+    stone: u64,
+    cache: &mut HashMap<(usize, u64), u64>,
+) -> u64 {
     if gen == N {
         1
     } else if cache.contains_key(&(gen, stone)) {
-        return cache[&(gen, stone)];
+        cache[&(gen, stone)]
     } else {
         let nbr = if stone == 0 {
             expand::<N>(gen + 1, 1, cache)
         } else {
             let nbr_digits = stone.checked_ilog10().unwrap_or(0) + 1;
-            if nbr_digits % 2 == 0 {
+            if nbr_digits.is_multiple_of(2) {
                 let div = 10u64.pow(nbr_digits / 2);
                 let left = stone / div;
                 let right = stone - left * div;
@@ -83,9 +97,19 @@ mod test {
             File::open("./inputs/day11-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part::<25, _>(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let line = input_file.lines().next().unwrap().unwrap();
+        let stones = super::parse_stones(line).expect("failed to parse input");
+        let res = super::part::<25>(&stones).expect("failed to run part_one");
 
         assert_eq!(55312, res);
     }
+
+    #[test]
+    fn invalid_stone() {
+        let err = super::parse_stones("125 17a".to_string()).expect_err("input should be invalid");
+        assert_eq!((1, 5, "17a"), (err.line, err.column, err.text.as_str()));
+
+        let err = super::parse_stones(String::new()).expect_err("input should be invalid");
+        assert_eq!((1, 1, ""), (err.line, err.column, err.text.as_str()));
+    }
 }
@@ -2,43 +2,53 @@ use num_rational::Rational64;
 use num_traits::identities::Zero;
 use regex::Regex;
 use std::sync::LazyLock;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    usize,
-};
 
-pub fn run_day() -> anyhow::Result<()> {
-    println!("=== DAY 13 ===");
+use crate::{error::ParseError, solution::Solution};
 
-    let res_part_one = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day13.txt").expect("failed to open input file"));
+pub struct Day13;
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_one(lines)?
-    };
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Claw Contraption";
 
-    println!("part one : {}", res_part_one);
+    type Input = Vec<Puzzle>;
+    type PartOne = i64;
+    type PartTwo = i64;
 
-    let res_part_two = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day13.txt").expect("failed to open input file"));
+    fn parse<I>(lines: I) -> anyhow::Result<Vec<Puzzle>>
+    where
+        I: Iterator<Item = String>,
+    {
+        Ok(parse_puzzles(lines)?)
+    }
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_two(lines)?
-    };
+    fn part_one(puzzles: &Vec<Puzzle>) -> anyhow::Result<i64> {
+        part_one(puzzles)
+    }
 
-    println!("part two : {}", res_part_two);
-    Ok(())
+    fn part_two(puzzles: &Vec<Puzzle>) -> anyhow::Result<i64> {
+        part_two(puzzles)
+    }
 }
 
+/// Solve the 2x2 linear system given as an augmented matrix.
+///
+/// Rows are swapped when a pivot is zero, so `None` is only returned when the matrix is
+/// singular, the system having either no solution or infinitely many.
 // thx to https://github.com/TheAlgorithms/Rust/blob/master/src/math/gaussian_elimination.rs
-pub fn gaussian_elimination(matrix: &mut [[Rational64; 3]; 2]) -> Vec<Rational64> {
+pub fn gaussian_elimination(matrix: &mut [[Rational64; 3]; 2]) -> Option<Vec<Rational64>> {
     let size = matrix.len();
     assert_eq!(size, matrix[0].len() - 1);
 
     for i in 0..size - 1 {
+        // A zero pivot only means the rows are in the wrong order, unless the whole column
+        // below it is zero too.
+        if matrix[i][i] == Rational64::zero() {
+            if let Some(row) = (i + 1..size).find(|&r| matrix[r][i] != Rational64::zero()) {
+                matrix.swap(i, row);
+            }
+        }
+
         for j in i..size - 1 {
             echelon(matrix, i, j);
         }
@@ -53,7 +63,7 @@ pub fn gaussian_elimination(matrix: &mut [[Rational64; 3]; 2]) -> Vec<Rational64
     #[allow(clippy::needless_range_loop)]
     for i in 0..size {
         if matrix[i][i] == Rational64::zero() {
-            println!("Infinitely many solutions");
+            return None;
         }
     }
 
@@ -61,7 +71,7 @@ pub fn gaussian_elimination(matrix: &mut [[Rational64; 3]; 2]) -> Vec<Rational64
     for i in 0..size {
         result[i] = matrix[i][size] / matrix[i][i];
     }
-    result
+    Some(result)
 }
 
 fn echelon(matrix: &mut [[Rational64; 3]; 2], i: usize, j: usize) {
@@ -94,53 +104,54 @@ static RE_BUTTON: LazyLock<Regex> =
 static RE_PRIZE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new("Prize: X=(\\d+), Y=(\\d+)").unwrap());
 
+/// Coordinates captured by `regex` on the next line, the `index`th one of the input.
+fn parse_line<I>(
+    lines: &mut I,
+    index: usize,
+    regex: &Regex,
+    expected: &str,
+) -> Result<(i64, i64), ParseError>
+where
+    I: Iterator<Item = String>,
+{
+    let line = lines.next().unwrap_or_default();
+    let captures = regex
+        .captures(&line)
+        .ok_or_else(|| ParseError::new(Day13::DAY, index, 0, line.as_str(), expected))?;
+
+    let coordinate = |i| {
+        let value = captures.get(i).unwrap();
+        value.as_str().parse::<i64>().map_err(|_| {
+            ParseError::new(Day13::DAY, index, value.start(), value.as_str(), "an i64")
+        })
+    };
+
+    Ok((coordinate(1)?, coordinate(2)?))
+}
+
+/// Claw machine, with the moves of its buttons and the position of its prize.
 #[derive(Debug)]
-struct Puzzle {
+pub struct Puzzle {
     a: (i64, i64),
     b: (i64, i64),
     prize: (i64, i64),
 }
 
 impl Puzzle {
-    fn new<I>(iter: &mut I) -> anyhow::Result<Self>
+    /// Machine described by the next three lines, the first one being the `index`th line of
+    /// the input.
+    fn new<I>(lines: &mut I, index: usize) -> Result<Self, ParseError>
     where
         I: Iterator<Item = String>,
     {
-        let a = iter
-            .next()
-            .ok_or_else(|| anyhow::anyhow!("failed to get first line"))?;
-        let b = iter
-            .next()
-            .ok_or_else(|| anyhow::anyhow!("failed to get second line"))?;
-        let res = iter
-            .next()
-            .ok_or_else(|| anyhow::anyhow!("failed to get third line"))?;
-
-        let (_, [x, y]) = RE_BUTTON
-            .captures(&a)
-            .ok_or_else(|| anyhow::anyhow!("wrong line"))?
-            .extract();
-
-        let a = (x.parse()?, y.parse()?);
-
-        let (_, [x, y]) = RE_BUTTON
-            .captures(&b)
-            .ok_or_else(|| anyhow::anyhow!("wrong line"))?
-            .extract();
-
-        let b = (x.parse()?, y.parse()?);
-
-        let (_, [x, y]) = RE_PRIZE
-            .captures(&res)
-            .ok_or_else(|| anyhow::anyhow!("wrong line"))?
-            .extract();
-
-        let prize = (x.parse()?, y.parse()?);
+        let a = parse_line(lines, index, &RE_BUTTON, "`Button A: X+x, Y+y`")?;
+        let b = parse_line(lines, index + 1, &RE_BUTTON, "`Button B: X+x, Y+y`")?;
+        let prize = parse_line(lines, index + 2, &RE_PRIZE, "`Prize: X=x, Y=y`")?;
 
         Ok(Self { a, b, prize })
     }
 
-    fn solve(&self) -> Option<(i64, i64)> {
+    fn solve(&self) -> Outcome {
         let mut matrixs = [
             [
                 Rational64::new(self.a.0, 1),
@@ -154,58 +165,159 @@ impl Puzzle {
             ],
         ];
 
-        let results = gaussian_elimination(&mut matrixs);
+        let Some(results) = gaussian_elimination(&mut matrixs) else {
+            return self.solve_collinear();
+        };
 
-        if results[0].denom() == &1 && results[1].denom() == &1 {
-            Some((*results[0].numer(), *results[1].numer()))
+        if results[0].denom() != &1 || results[1].denom() != &1 {
+            Outcome::NonIntegral
+        } else if results[0] < Rational64::zero() || results[1] < Rational64::zero() {
+            Outcome::NoSolution
         } else {
-            None
+            Outcome::Unique(*results[0].numer(), *results[1].numer())
         }
     }
+
+    /// Solve a machine whose buttons move the claw along the same line.
+    fn solve_collinear(&self) -> Outcome {
+        let cross = |(x1, y1): (i64, i64), (x2, y2): (i64, i64)| {
+            x1 as i128 * y2 as i128 - y1 as i128 * x2 as i128
+        };
+
+        // Every move is along the direction of a non-zero button, the prize must be too.
+        let on_line = match [self.a, self.b].into_iter().find(|v| *v != (0, 0)) {
+            Some(direction) => cross(direction, self.prize) == 0,
+            None => self.prize == (0, 0),
+        };
+        if !on_line {
+            return Outcome::NoSolution;
+        }
+
+        // Both coordinates give the same equation, the one with non-zero moves is solved.
+        let (a, b, prize) = if self.a.0 != 0 || self.b.0 != 0 {
+            (self.a.0, self.b.0, self.prize.0)
+        } else {
+            (self.a.1, self.b.1, self.prize.1)
+        };
+
+        Outcome::Infinite(cheapest_combination(a, b, prize))
+    }
 }
 
-fn part_one<I>(mut lines: I) -> anyhow::Result<i64>
-where
-    I: Iterator<Item = String>,
-{
-    let mut total = 0;
-    loop {
-        let new_puzzle = Puzzle::new(&mut lines)?;
+/// Result of solving a machine for the number of presses of each button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    /// Exactly one way to reach the prize.
+    Unique(i64, i64),
+    /// The prize cannot be reached, or only with a negative number of presses.
+    NoSolution,
+    /// The buttons are collinear and the prize on their line, along with the cheapest way to
+    /// reach it with whole presses if there is one.
+    Infinite(Option<(i64, i64)>),
+    /// The only way to reach the prize would need fractions of presses.
+    NonIntegral,
+}
 
-        if let Some((a, b)) = new_puzzle.solve() {
-            total += a * 3 + b;
+impl Outcome {
+    /// Cheapest presses of both buttons reaching the prize, if any.
+    fn presses(self) -> Option<(i64, i64)> {
+        match self {
+            Outcome::Unique(a, b) => Some((a, b)),
+            Outcome::Infinite(cheapest) => cheapest,
+            Outcome::NoSolution | Outcome::NonIntegral => None,
         }
+    }
 
-        if lines.next().is_none() {
-            break;
-        }
+    /// Tokens needed to win the prize, 0 if it cannot be won.
+    fn tokens(self) -> i64 {
+        self.presses().map(|(a, b)| a * 3 + b).unwrap_or(0)
     }
+}
 
-    Ok(total)
+/// Non-negative `(i, j)` with `i * a + j * b = target` costing the least tokens, for
+/// non-negative `a` and `b`.
+///
+/// Solutions are `(i0 + t * b / g, j0 - t * a / g)` with `g = gcd(a, b)`. The cost is linear in
+/// `t`, so the cheapest one is at a bound of the range of `t` giving non-negative presses.
+fn cheapest_combination(a: i64, b: i64, target: i64) -> Option<(i64, i64)> {
+    let (a, b, target) = (a as i128, b as i128, target as i128);
+
+    let (g, x, y) = extended_gcd(a, b);
+    if g == 0 {
+        return (target == 0).then_some((0, 0));
+    }
+    if target % g != 0 {
+        return None;
+    }
+
+    let (i0, j0) = (x * (target / g), y * (target / g));
+    let (di, dj) = (b / g, a / g);
+
+    // Smallest `t` with `i0 + t * di >= 0` and largest one with `j0 - t * dj >= 0`.
+    let low = (di != 0).then(|| -i0.div_euclid(di));
+    let high = (dj != 0).then(|| j0.div_euclid(dj));
+
+    let t = match (3 * di - dj).signum() {
+        1 => low,
+        -1 => high,
+        _ => low.or(high),
+    }
+    .unwrap_or(0);
+
+    let (i, j) = (i0 + t * di, j0 - t * dj);
+    (i >= 0 && j >= 0).then_some((i as i64, j as i64))
 }
 
-fn part_two<I>(mut lines: I) -> anyhow::Result<i64>
+/// `(g, x, y)` with `g = gcd(a, b) = x * a + y * b`.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// Machines of the input, separated by empty lines.
+fn parse_puzzles<I>(mut lines: I) -> Result<Vec<Puzzle>, ParseError>
 where
     I: Iterator<Item = String>,
 {
-    // 10000000000000
-    let mut total = 0;
+    let mut puzzles = Vec::new();
+    let mut index = 0;
     loop {
-        let mut new_puzzle = Puzzle::new(&mut lines)?;
-        new_puzzle.prize = (
-            new_puzzle.prize.0 + 10000000000000,
-            new_puzzle.prize.1 + 10000000000000,
-        );
-
-        if let Some((a, b)) = new_puzzle.solve() {
-            total += a * 3 + b;
-        }
+        puzzles.push(Puzzle::new(&mut lines, index)?);
+        index += 4;
 
         if lines.next().is_none() {
             break;
         }
     }
 
+    Ok(puzzles)
+}
+
+fn part_one(puzzles: &[Puzzle]) -> anyhow::Result<i64> {
+    let total = puzzles.iter().map(|puzzle| puzzle.solve().tokens()).sum();
+
+    Ok(total)
+}
+
+fn part_two(puzzles: &[Puzzle]) -> anyhow::Result<i64> {
+    let total = puzzles
+        .iter()
+        .map(|puzzle| {
+            let far = Puzzle {
+                prize: (
+                    puzzle.prize.0 + 10000000000000,
+                    puzzle.prize.1 + 10000000000000,
+                ),
+                ..*puzzle
+            };
+            far.solve().tokens()
+        })
+        .sum();
+
     Ok(total)
 }
 
@@ -216,16 +328,96 @@ mod test {
         io::{BufRead, BufReader},
     };
 
+    use super::{Outcome, Puzzle};
+
     #[test]
     fn part_one() {
         let input_file = BufReader::new(
             File::open("./inputs/day13-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input = super::parse_puzzles(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
 
         assert_eq!(480, res);
     }
 
+    #[test]
+    fn part_two() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day13-test.txt").expect("failed to open input file"),
+        );
+
+        let input = super::parse_puzzles(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_two");
+
+        assert_eq!(875318608908, res);
+    }
+
+    #[test]
+    fn solve() {
+        let puzzle = |a, b, prize| Puzzle { a, b, prize };
+
+        assert_eq!(
+            Outcome::Unique(80, 40),
+            puzzle((94, 34), (22, 67), (8400, 5400)).solve()
+        );
+        assert_eq!(
+            Outcome::NonIntegral,
+            puzzle((26, 66), (67, 21), (12748, 12176)).solve()
+        );
+        assert_eq!(Outcome::NoSolution, puzzle((1, 0), (0, 1), (-1, 5)).solve());
+
+        // Buttons not moving along an axis give zero pivots unless the rows are swapped.
+        assert_eq!(
+            Outcome::Unique(15, 1),
+            puzzle((0, 2), (2, 1), (2, 31)).solve()
+        );
+        assert_eq!(
+            Outcome::Unique(2, 2),
+            puzzle((0, 3), (2, 0), (4, 6)).solve()
+        );
+        assert_eq!(46, puzzle((0, 2), (2, 1), (2, 31)).solve().tokens());
+
+        // Collinear buttons, where pressing A is cheaper per step than pressing B.
+        assert_eq!(
+            Outcome::Infinite(Some((2, 3))),
+            puzzle((6, 6), (1, 1), (15, 15)).solve()
+        );
+        // Collinear buttons, where pressing B is cheaper per step than pressing A.
+        assert_eq!(
+            Outcome::Infinite(Some((1, 2))),
+            puzzle((4, 2), (6, 3), (16, 8)).solve()
+        );
+        assert_eq!(
+            Outcome::Infinite(None),
+            puzzle((4, 4), (6, 6), (5, 5)).solve()
+        );
+        assert_eq!(Outcome::NoSolution, puzzle((2, 2), (1, 1), (5, 6)).solve());
+    }
+
+    #[test]
+    fn invalid_machine() {
+        let lines = [
+            "Button A: X+94, Y+34",
+            "Button B: X+22, Y+67",
+            "Prize: X=8400, Y=5400",
+            "",
+            "Button A: X+26, Y+66",
+            "Button B: X+67, Y-21",
+        ]
+        .map(String::from);
+
+        let err = super::parse_puzzles(lines.into_iter()).expect_err("input should be invalid");
+        assert_eq!(
+            (6, 1, "Button B: X+67, Y-21"),
+            (err.line, err.column, err.text.as_str())
+        );
+
+        let lines = ["Button A: X+94, Y+34"].map(String::from);
+        let err = Puzzle::new(&mut lines.into_iter(), 0).expect_err("input should be invalid");
+        assert_eq!((2, 1, ""), (err.line, err.column, err.text.as_str()));
+    }
 }
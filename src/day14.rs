@@ -0,0 +1,206 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::{error::ParseError, grid::Point, solution::Solution};
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Restroom Redoubt";
+
+    type Input = Vec<Robot>;
+    type PartOne = usize;
+    type PartTwo = i64;
+
+    fn parse<I>(lines: I) -> anyhow::Result<Vec<Robot>>
+    where
+        I: Iterator<Item = String>,
+    {
+        Ok(parse_robots(lines)?)
+    }
+
+    fn part_one(robots: &Vec<Robot>) -> anyhow::Result<usize> {
+        part_one(robots, 101, 103)
+    }
+
+    fn part_two(robots: &Vec<Robot>) -> anyhow::Result<i64> {
+        part_two(robots, 101, 103)
+    }
+}
+
+static RE_ROBOT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new("^p=(-?\\d+),(-?\\d+) v=(-?\\d+),(-?\\d+)$").unwrap());
+
+#[derive(Debug, Clone, Copy)]
+pub struct Robot {
+    position: Point,
+    velocity: Point,
+}
+
+impl Robot {
+    /// Position after `seconds`, wrapping around the edges of a `width`x`height` space.
+    fn position_at(&self, seconds: i64, width: i64, height: i64) -> Point {
+        let p = self.position + self.velocity * seconds;
+        Point::new(p.x.rem_euclid(width), p.y.rem_euclid(height))
+    }
+}
+
+fn parse_robots<I>(lines: I) -> Result<Vec<Robot>, ParseError>
+where
+    I: Iterator<Item = String>,
+{
+    lines
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| {
+            let captures = RE_ROBOT.captures(&line).ok_or_else(|| {
+                ParseError::new(Day14::DAY, index, 0, line.as_str(), "`p=x,y v=dx,dy`")
+            })?;
+
+            let values = (1..=4)
+                .map(|i| {
+                    let value = captures.get(i).unwrap();
+                    value.as_str().parse::<i64>().map_err(|_| {
+                        ParseError::new(Day14::DAY, index, value.start(), value.as_str(), "an i64")
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(Robot {
+                position: Point::new(values[0], values[1]),
+                velocity: Point::new(values[2], values[3]),
+            })
+        })
+        .collect()
+}
+
+/// Product of the number of robots in each quadrant of a `width`x`height` space after 100
+/// seconds, robots on the middle lines not being counted.
+pub fn part_one(robots: &[Robot], width: i64, height: i64) -> anyhow::Result<usize> {
+    let mut quadrants = [0; 4];
+    for robot in robots {
+        let p = robot.position_at(100, width, height);
+        if p.x == width / 2 || p.y == height / 2 {
+            continue;
+        }
+
+        let quadrant = (p.x > width / 2) as usize + 2 * (p.y > height / 2) as usize;
+        quadrants[quadrant] += 1;
+    }
+
+    Ok(quadrants.iter().product())
+}
+
+fn variance(values: impl Iterator<Item = i64> + Clone) -> f64 {
+    let count = values.clone().count() as f64;
+    let mean = values.clone().sum::<i64>() as f64 / count;
+    values.map(|v| (v as f64 - mean).powi(2)).sum::<f64>() / count
+}
+
+/// Time in `0..period` at which the coordinates given by `coordinate` are the least spread.
+fn tightest<F>(robots: &[Robot], period: i64, coordinate: F) -> i64
+where
+    F: Fn(&Robot, i64) -> i64,
+{
+    (0..period)
+        .min_by(|a, b| {
+            let a = variance(robots.iter().map(|r| coordinate(r, *a)));
+            let b = variance(robots.iter().map(|r| coordinate(r, *b)));
+            a.total_cmp(&b)
+        })
+        .unwrap()
+}
+
+/// First second at which the robots of a `width`x`height` space draw a picture.
+///
+/// The picture gathers most robots in a small area, so both coordinates have their lowest
+/// variance at that time. The x coordinates repeat every `width` seconds and the y coordinates
+/// every `height` seconds, so the best time of each axis is found independently and both are
+/// combined with the chinese remainder theorem.
+pub fn part_two(robots: &[Robot], width: i64, height: i64) -> anyhow::Result<i64> {
+    if robots.is_empty() {
+        anyhow::bail!("no robots in the input");
+    }
+
+    let tx = tightest(robots, width, |r, t| r.position_at(t, width, height).x);
+    let ty = tightest(robots, height, |r, t| r.position_at(t, width, height).y);
+
+    // t = tx + k * width, with t = ty (mod height).
+    let inverse = mod_inverse(width, height)
+        .ok_or_else(|| anyhow::anyhow!("{} and {} are not coprime", width, height))?;
+    let k = ((ty - tx) * inverse).rem_euclid(height);
+
+    Ok(tx + k * width)
+}
+
+/// Inverse of `a` modulo `m`, if they are coprime.
+fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (mut old_r, mut r) = (a.rem_euclid(m), m);
+    let (mut old_s, mut s) = (1, 0);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+    }
+
+    if old_r == 1 {
+        Some(old_s.rem_euclid(m))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fs::File,
+        io::{BufRead, BufReader},
+    };
+
+    use crate::grid::Point;
+
+    #[test]
+    fn part_one() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day14-test.txt").expect("failed to open input file"),
+        );
+
+        let input = super::parse_robots(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input, 11, 7).expect("failed to run part_one");
+
+        assert_eq!(12, res);
+    }
+
+    #[test]
+    fn part_two() {
+        // Robots gathered around (5, 3) after 30 seconds, rewound to their starting positions.
+        let velocities = [
+            (1, 1),
+            (2, -1),
+            (-3, 2),
+            (4, 3),
+            (-1, -2),
+            (3, -3),
+            (-2, 1),
+            (5, 2),
+        ];
+        let lines = velocities.iter().enumerate().map(|(i, (dx, dy))| {
+            let p = Point::new(5 + (i % 2) as i64, 3 + (i / 4) as i64) - Point::new(*dx, *dy) * 30;
+            format!(
+                "p={},{} v={},{}",
+                p.x.rem_euclid(11),
+                p.y.rem_euclid(7),
+                dx,
+                dy
+            )
+        });
+
+        let robots = super::parse_robots(lines).expect("failed to parse input");
+        let res = super::part_two(&robots, 11, 7).expect("failed to run part_two");
+
+        assert_eq!(30, res);
+    }
+}
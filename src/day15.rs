@@ -0,0 +1,335 @@
+use std::fmt;
+
+use crate::{
+    error::ParseError,
+    grid::{Direction, Grid, Point},
+    solution::Solution,
+};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "Warehouse Woes";
+
+    type Input = (Warehouse, Vec<Direction>);
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse<I>(lines: I) -> anyhow::Result<(Warehouse, Vec<Direction>)>
+    where
+        I: Iterator<Item = String>,
+    {
+        parse(lines)
+    }
+
+    fn part_one(input: &(Warehouse, Vec<Direction>)) -> anyhow::Result<i64> {
+        part_one(input)
+    }
+
+    fn part_two(input: &(Warehouse, Vec<Direction>)) -> anyhow::Result<i64> {
+        part_two(input)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CellType {
+    Empty,
+    Wall,
+    Box,
+    /// Left half of a wide box.
+    BoxLeft,
+    /// Right half of a wide box.
+    BoxRight,
+}
+
+impl fmt::Display for CellType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            CellType::Empty => '.',
+            CellType::Wall => '#',
+            CellType::Box => 'O',
+            CellType::BoxLeft => '[',
+            CellType::BoxRight => ']',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+/// Map of the warehouse along with the position of the robot.
+#[derive(Debug, Clone)]
+pub struct Warehouse {
+    map: Grid<CellType>,
+    robot: Point,
+}
+
+impl Warehouse {
+    /// Same warehouse with everything except the robot twice as wide.
+    fn widen(&self) -> Self {
+        let rows = (0..self.map.height() as i64).map(|y| {
+            (0..self.map.width() as i64)
+                .flat_map(|x| match self.map[Point::new(x, y)] {
+                    CellType::Box => [CellType::BoxLeft, CellType::BoxRight],
+                    cell => [cell, cell],
+                })
+                .collect::<Vec<_>>()
+        });
+
+        Self {
+            map: Grid::from_rows(rows).expect("rows of a grid have the same length"),
+            robot: Point::new(self.robot.x * 2, self.robot.y),
+        }
+    }
+
+    /// Move the robot, pushing the boxes in front of it unless they are blocked by a wall.
+    ///
+    /// The edges of the map block like walls, for maps without a wall border.
+    fn push(&mut self, dir: Direction) {
+        let vertical = matches!(dir, Direction::Up | Direction::Down);
+
+        // Everything that moves, in the order it was reached from the robot.
+        let mut moved = vec![self.robot];
+        let mut i = 0;
+        while i < moved.len() {
+            let next = moved[i] + dir;
+            i += 1;
+
+            let pushed = match self.map.get(next) {
+                None | Some(CellType::Wall) => return,
+                Some(CellType::Empty) => continue,
+                Some(CellType::Box) => [Some(next), None],
+                Some(CellType::BoxLeft) if vertical => [Some(next), Some(next + Direction::Right)],
+                Some(CellType::BoxRight) if vertical => [Some(next), Some(next + Direction::Left)],
+                Some(CellType::BoxLeft | CellType::BoxRight) => [Some(next), None],
+            };
+
+            for p in pushed.into_iter().flatten() {
+                if !moved.contains(&p) {
+                    moved.push(p);
+                }
+            }
+        }
+
+        // The robot is not on the map, moving it only moves an empty cell.
+        for p in moved.into_iter().rev() {
+            self.map[p + dir] = self.map[p];
+            self.map[p] = CellType::Empty;
+        }
+        self.robot = self.robot + dir;
+    }
+
+    /// Sum of the GPS coordinates of the boxes.
+    pub fn gps(&self) -> i64 {
+        self.map
+            .iter()
+            .filter(|(_, c)| matches!(c, CellType::Box | CellType::BoxLeft))
+            .map(|(p, _)| 100 * p.y + p.x)
+            .sum()
+    }
+}
+
+impl fmt::Display for Warehouse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.map.height() as i64 {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..self.map.width() as i64 {
+                let p = Point::new(x, y);
+                if p == self.robot {
+                    write!(f, "@")?;
+                } else {
+                    write!(f, "{}", self.map[p])?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn extract_map<I>(lines: I) -> anyhow::Result<Warehouse>
+where
+    I: Iterator<Item = String>,
+{
+    let mut robot = None;
+    let map = Grid::from_lines(lines)?.try_map(|p, c| match c {
+        '#' => Ok(CellType::Wall),
+        '.' => Ok(CellType::Empty),
+        'O' => Ok(CellType::Box),
+        '@' if robot.is_none() => {
+            robot = Some(p);
+            Ok(CellType::Empty)
+        }
+        _ => Err(ParseError::new(
+            Day15::DAY,
+            p.y as usize,
+            p.x as usize,
+            *c,
+            "one of `#`, `.`, `O` or a single `@`",
+        )),
+    })?;
+
+    let robot = robot.ok_or_else(|| anyhow::anyhow!("no robot in the warehouse"))?;
+    Ok(Warehouse { map, robot })
+}
+
+fn extract_moves<I>(lines: I, offset: usize) -> Result<Vec<Direction>, ParseError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut moves = Vec::new();
+    for (index, line) in lines.enumerate() {
+        for (column, c) in line.chars().enumerate() {
+            let dir = match c {
+                '^' => Direction::Up,
+                '>' => Direction::Right,
+                '<' => Direction::Left,
+                'v' => Direction::Down,
+                _ => {
+                    return Err(ParseError::new(
+                        Day15::DAY,
+                        offset + index,
+                        column,
+                        c,
+                        "one of `^`, `>`, `<` or `v`",
+                    ))
+                }
+            };
+            moves.push(dir);
+        }
+    }
+    Ok(moves)
+}
+
+/// Warehouse described at the top of the input, and the moves of the robot below it.
+pub fn parse<I>(mut lines: I) -> anyhow::Result<(Warehouse, Vec<Direction>)>
+where
+    I: Iterator<Item = String>,
+{
+    let warehouse = extract_map(lines.by_ref())?;
+    let moves = extract_moves(lines, warehouse.map.height() + 1)?;
+
+    Ok((warehouse, moves))
+}
+
+/// State of the warehouse once the robot made every move, on the doubled-width map if `wide`.
+pub fn simulate(warehouse: &Warehouse, moves: &[Direction], wide: bool) -> Warehouse {
+    let mut warehouse = if wide {
+        warehouse.widen()
+    } else {
+        warehouse.clone()
+    };
+
+    for dir in moves {
+        warehouse.push(*dir);
+    }
+
+    warehouse
+}
+
+fn part_one((warehouse, moves): &(Warehouse, Vec<Direction>)) -> anyhow::Result<i64> {
+    Ok(simulate(warehouse, moves, false).gps())
+}
+
+fn part_two((warehouse, moves): &(Warehouse, Vec<Direction>)) -> anyhow::Result<i64> {
+    Ok(simulate(warehouse, moves, true).gps())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fs::File,
+        io::{BufRead, BufReader},
+    };
+
+    #[test]
+    fn part_one() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day15-test.txt").expect("failed to open input file"),
+        );
+
+        let input =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
+
+        assert_eq!(10092, res);
+    }
+
+    #[test]
+    fn part_one_small() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day15-test2.txt").expect("failed to open input file"),
+        );
+
+        let (warehouse, moves) =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let warehouse = super::simulate(&warehouse, &moves, false);
+
+        assert_eq!(2028, warehouse.gps());
+        assert_eq!(
+            "########\n\
+             #....OO#\n\
+             ##.....#\n\
+             #.....O#\n\
+             #.#O@..#\n\
+             #...O..#\n\
+             #...O..#\n\
+             ########",
+            warehouse.to_string()
+        );
+    }
+
+    #[test]
+    fn part_two() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day15-test.txt").expect("failed to open input file"),
+        );
+
+        let input =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_two");
+
+        assert_eq!(9021, res);
+    }
+
+    #[test]
+    fn part_two_render() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day15-test.txt").expect("failed to open input file"),
+        );
+
+        let (warehouse, moves) =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let warehouse = super::simulate(&warehouse, &moves, true);
+
+        assert_eq!(
+            "####################\n\
+             ##[].......[].[][]##\n\
+             ##[]...........[].##\n\
+             ##[]........[][][]##\n\
+             ##[]......[]....[]##\n\
+             ##..##......[]....##\n\
+             ##..[]............##\n\
+             ##..@......[].[][]##\n\
+             ##......[][]..[]..##\n\
+             ####################",
+            warehouse.to_string()
+        );
+    }
+
+    #[test]
+    fn no_wall_border() {
+        let lines = ["@O.", "", ">>>><<<<<"].map(String::from);
+
+        let (warehouse, moves) = super::parse(lines.into_iter()).expect("failed to parse input");
+
+        assert_eq!(
+            "@.O",
+            super::simulate(&warehouse, &moves, false).to_string()
+        );
+        assert_eq!(
+            "@...[]",
+            super::simulate(&warehouse, &moves, true).to_string()
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use fxhash::FxHashSet;
+
+use crate::{
+    error::ParseError,
+    grid::{Direction, Grid, Point},
+    pathfinding,
+    solution::Solution,
+};
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "Reindeer Maze";
+
+    type Input = Maze;
+    type PartOne = u64;
+    type PartTwo = usize;
+
+    fn parse<I>(lines: I) -> anyhow::Result<Maze>
+    where
+        I: Iterator<Item = String>,
+    {
+        extract_map(lines)
+    }
+
+    fn part_one(maze: &Maze) -> anyhow::Result<u64> {
+        part_one(maze)
+    }
+
+    fn part_two(maze: &Maze) -> anyhow::Result<usize> {
+        part_two(maze)
+    }
+}
+
+const STEP_COST: u64 = 1;
+const TURN_COST: u64 = 1000;
+
+/// Position of the reindeer and the direction it is facing.
+type State = (Point, Direction);
+
+/// Walls of the maze, with the start and end tiles of the race.
+pub struct Maze {
+    walls: Grid<bool>,
+    start: Point,
+    end: Point,
+}
+
+impl Maze {
+    /// States reachable from `state` by moving forward or turning, with their cost.
+    fn moves(&self, (position, dir): State) -> impl Iterator<Item = (State, u64)> + '_ {
+        let forward = position + dir;
+        let step = (self.walls.get(forward) == Some(&false)).then_some(((forward, dir), STEP_COST));
+
+        step.into_iter().chain([
+            ((position, dir.turn_left()), TURN_COST),
+            ((position, dir.turn_right()), TURN_COST),
+        ])
+    }
+}
+
+fn extract_map<I>(lines: I) -> anyhow::Result<Maze>
+where
+    I: Iterator<Item = String>,
+{
+    let mut start = None;
+    let mut end = None;
+    let walls = Grid::from_lines(lines)?.try_map(|p, c| match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        'S' if start.is_none() => {
+            start = Some(p);
+            Ok(false)
+        }
+        'E' if end.is_none() => {
+            end = Some(p);
+            Ok(false)
+        }
+        _ => Err(ParseError::new(
+            Day16::DAY,
+            p.y as usize,
+            p.x as usize,
+            *c,
+            "one of `#`, `.` or a single `S` and `E`",
+        )),
+    })?;
+
+    Ok(Maze {
+        walls,
+        start: start.ok_or_else(|| anyhow::anyhow!("no start tile in the maze"))?,
+        end: end.ok_or_else(|| anyhow::anyhow!("no end tile in the maze"))?,
+    })
+}
+
+fn part_one(maze: &Maze) -> anyhow::Result<u64> {
+    let (_, score) = pathfinding::dijkstra(
+        (maze.start, Direction::Right),
+        |state| maze.moves(*state),
+        |(position, _)| *position == maze.end,
+    )
+    .ok_or_else(|| anyhow::anyhow!("the end tile is not reachable"))?;
+
+    Ok(score)
+}
+
+fn part_two(maze: &Maze) -> anyhow::Result<usize> {
+    let paths = pathfinding::dijkstra_all(
+        (maze.start, Direction::Right),
+        |state| maze.moves(*state),
+        |(position, _)| *position == maze.end,
+    )
+    .ok_or_else(|| anyhow::anyhow!("the end tile is not reachable"))?;
+
+    let tiles = paths
+        .nodes()
+        .into_iter()
+        .map(|(position, _)| position)
+        .collect::<FxHashSet<_>>();
+
+    Ok(tiles.len())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fs::File,
+        io::{BufRead, BufReader},
+    };
+
+    #[test]
+    fn part_one() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day16-test.txt").expect("failed to open input file"),
+        );
+
+        let input = super::extract_map(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
+
+        assert_eq!(7036, res);
+    }
+
+    #[test]
+    fn part_one_second_example() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day16-test2.txt").expect("failed to open input file"),
+        );
+
+        let input = super::extract_map(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
+
+        assert_eq!(11048, res);
+    }
+
+    #[test]
+    fn part_two() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day16-test.txt").expect("failed to open input file"),
+        );
+
+        let input = super::extract_map(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_two");
+
+        assert_eq!(45, res);
+    }
+
+    #[test]
+    fn part_two_second_example() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day16-test2.txt").expect("failed to open input file"),
+        );
+
+        let input = super::extract_map(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_two");
+
+        assert_eq!(64, res);
+    }
+}
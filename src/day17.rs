@@ -0,0 +1,379 @@
+use std::fmt::Write;
+
+use crate::{error::ParseError, solution::Solution};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    const TITLE: &'static str = "Chronospatial Computer";
+
+    type Input = Computer;
+    type PartOne = String;
+    type PartTwo = u64;
+
+    fn parse<I>(lines: I) -> anyhow::Result<Computer>
+    where
+        I: Iterator<Item = String>,
+    {
+        parse_computer(lines)
+    }
+
+    fn part_one(computer: &Computer) -> anyhow::Result<String> {
+        part_one(computer)
+    }
+
+    fn part_two(computer: &Computer) -> anyhow::Result<u64> {
+        part_two(computer)
+    }
+}
+
+const MNEMONICS: [&str; 8] = ["adv", "bxl", "bst", "jnz", "bxc", "out", "bdv", "cdv"];
+
+/// Instructions run before a program is considered to never halt.
+const STEP_LIMIT: usize = 1_000_000;
+
+/// Three-bit computer, with its registers and program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Computer {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+    pub program: Vec<u8>,
+}
+
+impl Computer {
+    fn combo(&self, operand: u8) -> anyhow::Result<u64> {
+        match operand {
+            0..=3 => Ok(operand as u64),
+            4 => Ok(self.a),
+            5 => Ok(self.b),
+            6 => Ok(self.c),
+            _ => anyhow::bail!("reserved combo operand {}", operand),
+        }
+    }
+
+    /// Register A divided by 2 to the power of the combo `operand`.
+    fn divide(&self, operand: u8) -> anyhow::Result<u64> {
+        let shift = u32::try_from(self.combo(operand)?).unwrap_or(u32::MAX);
+        Ok(self.a.checked_shr(shift).unwrap_or(0))
+    }
+
+    /// Run the program until it halts, returning everything it outputs.
+    ///
+    /// Fails on the reserved combo operand, or when the program is still running after
+    /// `STEP_LIMIT` instructions.
+    pub fn run(&mut self) -> anyhow::Result<Vec<u8>> {
+        let mut output = Vec::new();
+        let mut ip = 0;
+
+        for _ in 0..STEP_LIMIT {
+            if ip + 1 >= self.program.len() {
+                return Ok(output);
+            }
+
+            let (opcode, operand) = (self.program[ip], self.program[ip + 1]);
+            ip += 2;
+
+            match opcode {
+                0 => self.a = self.divide(operand)?,
+                1 => self.b ^= operand as u64,
+                2 => self.b = self.combo(operand)? % 8,
+                3 => {
+                    if self.a != 0 {
+                        ip = operand as usize;
+                    }
+                }
+                4 => self.b ^= self.c,
+                5 => output.push((self.combo(operand)? % 8) as u8),
+                6 => self.b = self.divide(operand)?,
+                7 => self.c = self.divide(operand)?,
+                _ => unreachable!("opcodes are three-bit values"),
+            }
+        }
+
+        anyhow::bail!("the program did not halt after {} instructions", STEP_LIMIT)
+    }
+}
+
+fn combo_name(operand: u8) -> String {
+    match operand {
+        0..=3 => operand.to_string(),
+        4 => "A".to_string(),
+        5 => "B".to_string(),
+        6 => "C".to_string(),
+        _ => "<reserved>".to_string(),
+    }
+}
+
+/// Readable listing of a program, one instruction per line.
+///
+/// ```text
+/// 00: bst A      ; B = A % 8
+/// 02: bxl 1      ; B = B ^ 1
+/// ```
+pub fn disassemble(program: &[u8]) -> String {
+    let mut listing = String::new();
+
+    for (i, instruction) in program.chunks(2).enumerate() {
+        let opcode = instruction[0];
+        let Some(&operand) = instruction.get(1) else {
+            writeln!(listing, "{:02}: {}", i * 2, opcode).unwrap();
+            continue;
+        };
+
+        let combo = combo_name(operand);
+        let (argument, effect) = match opcode {
+            0 => (combo.clone(), format!("A = A >> {}", combo)),
+            1 => (operand.to_string(), format!("B = B ^ {}", operand)),
+            2 => (combo.clone(), format!("B = {} % 8", combo)),
+            3 => (
+                operand.to_string(),
+                format!("if A != 0 jump {:02}", operand),
+            ),
+            4 => (operand.to_string(), "B = B ^ C".to_string()),
+            5 => (combo.clone(), format!("out {} % 8", combo)),
+            6 => (combo.clone(), format!("B = A >> {}", combo)),
+            _ => (combo.clone(), format!("C = A >> {}", combo)),
+        };
+
+        let instruction = format!("{} {}", MNEMONICS[opcode as usize], argument);
+        writeln!(listing, "{:02}: {:<10} ; {}", i * 2, instruction, effect).unwrap();
+    }
+
+    listing
+}
+
+/// Computer described by the puzzle input, with its registers and program.
+pub fn parse_computer<I>(lines: I) -> anyhow::Result<Computer>
+where
+    I: Iterator<Item = String>,
+{
+    let mut registers = [0; 3];
+    let mut program = None;
+
+    for (index, line) in lines.enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some((register, value)) = line
+            .strip_prefix("Register ")
+            .and_then(|l| l.split_once(": "))
+        {
+            let register = match register {
+                "A" => 0,
+                "B" => 1,
+                "C" => 2,
+                _ => {
+                    return Err(ParseError::new(
+                        Day17::DAY,
+                        index,
+                        "Register ".len(),
+                        register,
+                        "`A`, `B` or `C`",
+                    )
+                    .into())
+                }
+            };
+            registers[register] = value.parse::<u64>().map_err(|_| {
+                ParseError::new(
+                    Day17::DAY,
+                    index,
+                    line.len() - value.len(),
+                    value,
+                    "a number",
+                )
+            })?;
+        } else if let Some(values) = line.strip_prefix("Program: ") {
+            let mut offset = "Program: ".len();
+            let values = values
+                .split(',')
+                .map(|v| {
+                    let value_offset = offset;
+                    offset += v.len() + 1;
+                    v.parse::<u8>().ok().filter(|v| *v < 8).ok_or_else(|| {
+                        ParseError::new(Day17::DAY, index, value_offset, v, "a three-bit number")
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            program = Some(values);
+        } else {
+            return Err(ParseError::new(
+                Day17::DAY,
+                index,
+                0,
+                line.as_str(),
+                "`Register X: N` or `Program: ...`",
+            )
+            .into());
+        }
+    }
+
+    let [a, b, c] = registers;
+    Ok(Computer {
+        a,
+        b,
+        c,
+        program: program.ok_or_else(|| anyhow::anyhow!("no program in the input"))?,
+    })
+}
+
+fn part_one(computer: &Computer) -> anyhow::Result<String> {
+    let mut computer = computer.clone();
+
+    let output = computer
+        .run()?
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    Ok(output)
+}
+
+/// Lowest value of register A, starting with the octal digits of `a`, making the program
+/// output itself, `found` of its last values being already output by `a`.
+///
+/// The program is expected to loop, shifting A by 3 bits every iteration and outputting a value
+/// depending on its lowest bits, so A is built one octal digit at a time starting from the
+/// last output.
+fn find_quine(computer: &Computer, a: u64, found: usize) -> anyhow::Result<Option<u64>> {
+    if found == computer.program.len() {
+        return Ok(Some(a));
+    }
+
+    let expected = &computer.program[computer.program.len() - found - 1..];
+    for digit in 0..8 {
+        let candidate = a << 3 | digit;
+        if candidate == 0 {
+            continue;
+        }
+
+        let mut run = Computer {
+            a: candidate,
+            ..computer.clone()
+        };
+        if run.run()? == expected {
+            if let Some(a) = find_quine(computer, candidate, found + 1)? {
+                return Ok(Some(a));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn part_two(computer: &Computer) -> anyhow::Result<u64> {
+    find_quine(computer, 0, 0)?
+        .ok_or_else(|| anyhow::anyhow!("no value of register A makes the program output itself"))
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fs::File,
+        io::{BufRead, BufReader},
+    };
+
+    use super::Computer;
+
+    #[test]
+    fn part_one() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day17-test.txt").expect("failed to open input file"),
+        );
+
+        let input = super::parse_computer(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
+
+        assert_eq!("4,6,3,5,6,3,5,2,1,0", res);
+    }
+
+    #[test]
+    fn instructions() {
+        let mut computer = Computer {
+            a: 0,
+            b: 0,
+            c: 9,
+            program: vec![2, 6],
+        };
+        computer.run().unwrap();
+        assert_eq!(1, computer.b);
+
+        let mut computer = Computer {
+            a: 2024,
+            b: 0,
+            c: 0,
+            program: vec![0, 1, 5, 4, 3, 0],
+        };
+        assert_eq!(
+            vec![4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0],
+            computer.run().unwrap()
+        );
+        assert_eq!(0, computer.a);
+
+        let mut computer = Computer {
+            a: 0,
+            b: 2024,
+            c: 43690,
+            program: vec![4, 0],
+        };
+        computer.run().unwrap();
+        assert_eq!(44354, computer.b);
+    }
+
+    #[test]
+    fn invalid_programs() {
+        // Shifting by 64 bits or more clears the register.
+        let mut computer = Computer {
+            a: u64::MAX,
+            b: 64,
+            c: 1,
+            program: vec![7, 5, 0, 5],
+        };
+        computer.run().unwrap();
+        assert_eq!((0, 0), (computer.a, computer.c));
+
+        let mut computer = Computer {
+            a: 1,
+            b: 0,
+            c: 0,
+            program: vec![5, 7],
+        };
+        assert!(computer.run().is_err());
+
+        // Jumping back to the start with A never changing loops forever.
+        let mut computer = Computer {
+            a: 1,
+            b: 0,
+            c: 0,
+            program: vec![3, 0],
+        };
+        assert!(computer.run().is_err());
+    }
+
+    #[test]
+    fn disassemble() {
+        assert_eq!(
+            "00: adv 3      ; A = A >> 3\n\
+             02: out A      ; out A % 8\n\
+             04: jnz 0      ; if A != 0 jump 00\n",
+            super::disassemble(&[0, 3, 5, 4, 3, 0])
+        );
+    }
+
+    #[test]
+    fn part_two() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day17-test2.txt").expect("failed to open input file"),
+        );
+
+        let input = super::parse_computer(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_two");
+
+        assert_eq!(117440, res);
+    }
+}
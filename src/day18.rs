@@ -1,115 +1,110 @@
-use std::collections::VecDeque;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+use crate::{
+    error::ParseError,
+    grid::{Grid, Point},
+    pathfinding,
+    solution::Solution,
 };
 
-pub fn run_day() -> anyhow::Result<()> {
-    println!("=== DAY 12 ===");
+pub struct Day18;
 
-    let res_part_one = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day18.txt").expect("failed to open input file"));
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    const TITLE: &'static str = "RAM Run";
 
-        let lines = input_file.lines().take(1024).map(|l| l.unwrap());
-        part_one(lines, 71)?
-    };
+    type Input = Vec<Point>;
+    type PartOne = usize;
+    type PartTwo = String;
 
-    println!("part one : {}", res_part_one);
+    fn parse<I>(lines: I) -> anyhow::Result<Vec<Point>>
+    where
+        I: Iterator<Item = String>,
+    {
+        parse_bytes(lines, MEMORY_SIZE)
+    }
 
-    let res_part_two = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day18.txt").expect("failed to open input file"));
+    fn part_one(bytes: &Vec<Point>) -> anyhow::Result<usize> {
+        part_one(&bytes[..bytes.len().min(1024)], MEMORY_SIZE)
+    }
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_two(lines, 71)?
-    };
+    fn part_two(bytes: &Vec<Point>) -> anyhow::Result<String> {
+        part_two(bytes, MEMORY_SIZE).map(|(x, y)| format!("{},{}", x, y))
+    }
+}
+
+/// Width and height of the memory space of the puzzle.
+const MEMORY_SIZE: usize = 71;
+
+/// Length of the shortest path from the top left corner to the bottom right corner of a grid
+/// of corrupted cells, if the exit is reachable.
+pub fn shortest_path(grid: &Grid<bool>) -> Option<usize> {
+    let end = Point::new(grid.width() as i64 - 1, grid.height() as i64 - 1);
 
-    println!("part two : {:?}", res_part_two);
-    Ok(())
+    let path = pathfinding::bfs(
+        Point::new(0, 0),
+        |p| grid.neighbours(*p).filter(|n| !grid[*n]),
+        |p| *p == end,
+    )?;
+    Some(path.len() - 1)
 }
 
-fn bfs(grid: &mut [Vec<(bool, bool, (usize, usize))>]) -> Option<usize> {
-    let mut queue = VecDeque::new();
-    grid[0][0].1 = true;
-
-    queue.push_back((0, 0));
-
-    while !queue.is_empty() {
-        let (xv, yv) = queue.pop_front().unwrap();
-        if xv == grid.len() - 1 && yv == grid.len() - 1 {
-            let mut n = 1;
-            let mut p = grid[yv][xv].2;
-            while p.0 != 0 || p.1 != 0 {
-                p = grid[p.1][p.0].2;
-                n += 1;
-            }
-            return Some(n);
-        } else {
-            if xv > 0 && !grid[yv][xv - 1].0 && !grid[yv][xv - 1].1 {
-                grid[yv][xv - 1].1 = true;
-                grid[yv][xv - 1].2 = (xv, yv);
-                queue.push_back((xv - 1, yv));
-            }
-
-            if xv + 1 < grid.len() && !grid[yv][xv + 1].0 && !grid[yv][xv + 1].1 {
-                grid[yv][xv + 1].1 = true;
-                grid[yv][xv + 1].2 = (xv, yv);
-                queue.push_back((xv + 1, yv));
-            }
-
-            if yv + 1 < grid.len() && !grid[yv + 1][xv].0 && !grid[yv + 1][xv].1 {
-                grid[yv + 1][xv].1 = true;
-                grid[yv + 1][xv].2 = (xv, yv);
-                queue.push_back((xv, yv + 1));
-            }
-
-            if yv > 0 && !grid[yv - 1][xv].0 && !grid[yv - 1][xv].1 {
-                grid[yv - 1][xv].1 = true;
-                grid[yv - 1][xv].2 = (xv, yv);
-                queue.push_back((xv, yv - 1));
-            }
-        }
+/// Position of a byte falling in `grid`.
+fn parse_byte(index: usize, line: &str, grid: &Grid<bool>) -> anyhow::Result<Point> {
+    let error = || ParseError::new(Day18::DAY, index, 0, line, "two coordinates");
+    let (x, y) = line.split_once(',').ok_or_else(error)?;
+    let x = x.parse::<usize>().map_err(|_| error())?;
+    let y = y.parse::<usize>().map_err(|_| error())?;
+
+    let byte = Point::new(x as i64, y as i64);
+    if !grid.contains(byte) {
+        let expected = format!(
+            "a position inside the {}x{} memory space",
+            grid.width(),
+            grid.height()
+        );
+        return Err(ParseError::new(Day18::DAY, index, 0, line, expected).into());
     }
-    None
+
+    Ok(byte)
 }
 
-fn part_one<I>(lines: I, size: usize) -> anyhow::Result<usize>
+/// Positions of the bytes falling in a `size`x`size` memory space, in the order they fall.
+pub fn parse_bytes<I>(lines: I, size: usize) -> anyhow::Result<Vec<Point>>
 where
     I: Iterator<Item = String>,
 {
-    let mut grid = (0..size)
-        .map(|_| vec![(false, false, (0, 0)); size])
-        .collect::<Vec<_>>();
-
-    for line in lines {
-        let (x, y) = line.split_once(",").unwrap();
-        let (x, y) = (x.parse::<usize>()?, y.parse::<usize>()?);
-        grid[y][x].0 = true;
+    let grid = memory_space(size);
+
+    lines
+        .enumerate()
+        .map(|(index, line)| parse_byte(index, &line, &grid))
+        .collect()
+}
+
+fn memory_space(size: usize) -> Grid<bool> {
+    Grid::new(size, size, false)
+}
+
+/// Shortest path on a `size`x`size` memory space, once every byte of `bytes` has fallen.
+///
+/// `bytes` must have been parsed for the same `size`.
+pub fn part_one(bytes: &[Point], size: usize) -> anyhow::Result<usize> {
+    let mut grid = memory_space(size);
+
+    for byte in bytes {
+        grid[*byte] = true;
     }
-    Ok(bfs(&mut grid).unwrap())
+    shortest_path(&grid).ok_or_else(|| anyhow::anyhow!("the exit is not reachable"))
 }
-fn part_two<I>(lines: I, size: usize) -> anyhow::Result<(usize, usize)>
-where
-    I: Iterator<Item = String>,
-{
-    let mut grid = (0..size)
-        .map(|_| vec![(false, false, (0, 0)); size])
-        .collect::<Vec<_>>();
-
-    for line in lines {
-        let (x, y) = line.split_once(",").unwrap();
-        let (x, y) = (x.parse::<usize>()?, y.parse::<usize>()?);
-        grid[y][x].0 = true;
-
-        for x in 0..size {
-            for y in 0..size {
-                grid[y][x].1 = false;
-            }
-        }
 
-        if bfs(&mut grid).is_none() {
-            return Ok((x, y));
+/// Coordinates of the first byte blocking the exit of a `size`x`size` memory space.
+pub fn part_two(bytes: &[Point], size: usize) -> anyhow::Result<(usize, usize)> {
+    let mut grid = memory_space(size);
+
+    for byte in bytes {
+        grid[*byte] = true;
+
+        if shortest_path(&grid).is_none() {
+            return Ok((byte.x as usize, byte.y as usize));
         }
     }
 
@@ -123,14 +118,17 @@ mod test {
         io::{BufRead, BufReader},
     };
 
+    use crate::error::ParseError;
+
     #[test]
     fn part_one() {
         let input_file = BufReader::new(
             File::open("./inputs/day18-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()).take(12), 7)
-            .expect("failed to run part_one");
+        let bytes = super::parse_bytes(input_file.lines().map(|l| l.unwrap()).take(12), 7)
+            .expect("failed to parse input");
+        let res = super::part_one(&bytes, 7).expect("failed to run part_one");
 
         assert_eq!(22, res);
     }
@@ -141,9 +139,22 @@ mod test {
             File::open("./inputs/day18-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_two(input_file.lines().map(|l| l.unwrap()), 7)
-            .expect("failed to run part_two");
+        let input = super::parse_bytes(input_file.lines().map(|l| l.unwrap()), 7)
+            .expect("failed to parse input");
+        let res = super::part_two(&input, 7).expect("failed to run part_two");
 
         assert_eq!((6, 1), res);
     }
+
+    #[test]
+    fn byte_outside_memory_space() {
+        let lines = ["1,1", "80,3"].map(String::from);
+
+        let err = super::parse_bytes(lines.into_iter(), 7).expect_err("input should be invalid");
+        let err = err
+            .downcast_ref::<ParseError>()
+            .expect("error should be a ParseError");
+
+        assert_eq!((2, "80,3"), (err.line, err.text.as_str()));
+    }
 }
@@ -1,31 +1,90 @@
-use std::collections::{HashMap, VecDeque};
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::collections::HashMap;
 
-pub fn run_day() -> anyhow::Result<()> {
-    println!("=== DAY 19 ===");
+use crate::{error::ParseError, solution::Solution};
 
-    let res_part_one = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day19.txt").expect("failed to open input file"));
+pub struct Day19;
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_one(lines)?
-    };
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    const TITLE: &'static str = "Linen Layout";
 
-    println!("part one : {}", res_part_one);
+    type Input = (Vec<String>, Vec<String>);
+    type PartOne = usize;
+    type PartTwo = usize;
 
-    let res_part_two = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day19.txt").expect("failed to open input file"));
+    fn parse<I>(lines: I) -> anyhow::Result<(Vec<String>, Vec<String>)>
+    where
+        I: Iterator<Item = String>,
+    {
+        Ok(parse_towels(lines)?)
+    }
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_two(lines)?
-    };
-    println!("part two : {:?}", res_part_two);
-    Ok(())
+    fn part_one(towels: &(Vec<String>, Vec<String>)) -> anyhow::Result<usize> {
+        part_one(towels)
+    }
+
+    fn part_two(towels: &(Vec<String>, Vec<String>)) -> anyhow::Result<usize> {
+        part_two(towels)
+    }
+}
+
+/// Position of the first character of `s` that is not a stripe colour.
+fn invalid_colour(s: &str) -> Option<usize> {
+    s.find(|c| !matches!(c, 'w' | 'u' | 'b' | 'r' | 'g'))
+}
+
+/// Towel patterns and designs to make, separated by an empty line.
+fn parse_towels<I>(mut lines: I) -> Result<(Vec<String>, Vec<String>), ParseError>
+where
+    I: Iterator<Item = String>,
+{
+    let line = lines.next().unwrap_or_default();
+    let mut offset = 0;
+    let patterns = line
+        .split(", ")
+        .map(|pattern| {
+            let pattern_offset = offset;
+            offset += pattern.len() + 2;
+            match invalid_colour(pattern) {
+                _ if pattern.is_empty() => Err(ParseError::new(
+                    Day19::DAY,
+                    0,
+                    pattern_offset,
+                    pattern,
+                    "a towel pattern",
+                )),
+                Some(column) => Err(ParseError::new(
+                    Day19::DAY,
+                    0,
+                    pattern_offset + column,
+                    pattern[column..].chars().next().unwrap(),
+                    "`w`, `u`, `b`, `r` or `g`",
+                )),
+                None => Ok(pattern.to_string()),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if let Some(line) = lines.next().filter(|l| !l.is_empty()) {
+        return Err(ParseError::new(Day19::DAY, 1, 0, line, "an empty line"));
+    }
+
+    let designs = lines
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| match invalid_colour(&line) {
+            Some(column) => Err(ParseError::new(
+                Day19::DAY,
+                index + 2,
+                column,
+                line[column..].chars().next().unwrap(),
+                "`w`, `u`, `b`, `r` or `g`",
+            )),
+            None => Ok(line),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((patterns, designs))
 }
 
 fn match_patterns(s: &str, patterns: &[String]) -> bool {
@@ -41,21 +100,10 @@ fn match_patterns(s: &str, patterns: &[String]) -> bool {
     false
 }
 
-fn part_one<I>(mut lines: I) -> anyhow::Result<usize>
-where
-    I: Iterator<Item = String>,
-{
-    let patterns = lines
-        .next()
-        .unwrap()
-        .split(", ")
-        .map(|s| s.to_string())
-        .collect::<Vec<_>>();
-    let _ = lines.next();
-
+fn part_one((patterns, designs): &(Vec<String>, Vec<String>)) -> anyhow::Result<usize> {
     let mut nbr = 0;
-    for l in lines {
-        if match_patterns(&l, &patterns) {
+    for l in designs {
+        if match_patterns(l, patterns) {
             nbr += 1;
         }
     }
@@ -88,22 +136,11 @@ fn match_patterns_counting(
     nbr
 }
 
-fn part_two<I>(mut lines: I) -> anyhow::Result<usize>
-where
-    I: Iterator<Item = String>,
-{
-    let patterns = lines
-        .next()
-        .unwrap()
-        .split(", ")
-        .map(|s| s.to_string())
-        .collect::<Vec<_>>();
-    let _ = lines.next();
-
+fn part_two((patterns, designs): &(Vec<String>, Vec<String>)) -> anyhow::Result<usize> {
     let mut nbr = 0;
     let mut cache = HashMap::new();
-    for l in lines {
-        nbr += match_patterns_counting(&l, &patterns, &mut cache);
+    for l in designs {
+        nbr += match_patterns_counting(l, patterns, &mut cache);
     }
 
     Ok(nbr)
@@ -122,8 +159,9 @@ mod test {
             File::open("./inputs/day19-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input = super::parse_towels(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
 
         assert_eq!(6, res);
     }
@@ -134,9 +172,24 @@ mod test {
             File::open("./inputs/day19-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_two(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_two");
+        let input = super::parse_towels(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_two");
 
         assert_eq!(16, res);
     }
+
+    #[test]
+    fn invalid_towels() {
+        let lines = ["r, wx, b", "", "brwrr"].map(String::from);
+        let err = super::parse_towels(lines.into_iter()).expect_err("input should be invalid");
+        assert_eq!((1, 5, "x"), (err.line, err.column, err.text.as_str()));
+
+        let lines = ["r, wr, b", "", "brwrr", "bggrq"].map(String::from);
+        let err = super::parse_towels(lines.into_iter()).expect_err("input should be invalid");
+        assert_eq!((4, 5, "q"), (err.line, err.column, err.text.as_str()));
+
+        let err = super::parse_towels(std::iter::empty()).expect_err("input should be invalid");
+        assert_eq!((1, 1, ""), (err.line, err.column, err.text.as_str()));
+    }
 }
@@ -1,49 +1,42 @@
-use std::collections::{HashSet, VecDeque};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-pub fn run_day() -> anyhow::Result<()> {
-    println!("=== DAY 20 ===");
-
-    let res_part_one = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day20.txt").expect("failed to open input file"));
-
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_one(lines, 100)?
-    };
-
-    println!("part one : {}", res_part_one);
-
-    let res_part_two = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day20.txt").expect("failed to open input file"));
+use crate::{
+    grid::{Grid, Point},
+    pathfinding,
+    solution::Solution,
+};
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    const TITLE: &'static str = "Race Condition";
+
+    type Input = Racetrack;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse<I>(lines: I) -> anyhow::Result<Racetrack>
+    where
+        I: Iterator<Item = String>,
+    {
+        parse(lines)
+    }
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_two(lines)?
-    };
-    println!("part two : {:?}", res_part_two);
-    Ok(())
-}
+    fn part_one(racetrack: &Racetrack) -> anyhow::Result<usize> {
+        part_one(racetrack, 100)
+    }
 
-struct State {
-    previous: (usize, usize),
-    visited: bool,
+    fn part_two(racetrack: &Racetrack) -> anyhow::Result<usize> {
+        part_two(racetrack, 100)
+    }
 }
 
 struct Cell {
     cell_type: CellType,
-    visited: bool,
-    ptr: (usize, usize),
 }
 
 impl Cell {
     fn new(cell_type: CellType) -> Self {
-        Self {
-            cell_type,
-            visited: false,
-            ptr: (0, 0),
-        }
+        Self { cell_type }
     }
 
     fn is_wall(&self) -> bool {
@@ -84,115 +77,79 @@ impl From<char> for TrackType {
     }
 }
 
-fn normal_track(grid: &mut Vec<Vec<Cell>>) -> usize {
-    let start = grid
-        .iter()
-        .enumerate()
-        .find_map(|(y, r)| {
-            r.iter()
-                .position(|c| c.cell_type == CellType::Track(TrackType::Start))
-                .map(|x| (x, y))
-        })
-        .unwrap();
-
-    let end = grid
-        .iter()
-        .enumerate()
-        .find_map(|(y, r)| {
-            r.iter()
-                .position(|c| c.cell_type == CellType::Track(TrackType::End))
-                .map(|x| (x, y))
-        })
-        .unwrap();
-
-    let mut queue = VecDeque::new();
-    grid[start.1][start.0].visited = true;
-    queue.push_back(start);
-
-    while !queue.is_empty() {
-        let (xv, yv) = queue.pop_front().unwrap();
-        if xv == end.0 && yv == end.1 {
-            let mut n = 1;
-            let mut p = grid[yv][xv].ptr;
-            while p != start {
-                p = grid[p.1][p.0].ptr;
-                n += 1;
-            }
-            return n;
-        } else {
-            if xv > 0 && !grid[yv][xv - 1].is_wall() && !grid[yv][xv - 1].visited {
-                grid[yv][xv - 1].visited = true;
-                grid[yv][xv - 1].ptr = (xv, yv);
-                queue.push_back((xv - 1, yv));
-            }
+/// Track from the start to the end, without cheating.
+fn normal_track(grid: &Grid<Cell>) -> anyhow::Result<Vec<Point>> {
+    let find = |track_type| {
+        grid.position(|c| c.cell_type == CellType::Track(track_type))
+            .ok_or_else(|| anyhow::anyhow!("no {:?} on the racetrack", track_type))
+    };
+    let start = find(TrackType::Start)?;
+    let end = find(TrackType::End)?;
+
+    pathfinding::bfs(
+        start,
+        |p| grid.neighbours(*p).filter(|n| !grid[*n].is_wall()),
+        |p| *p == end,
+    )
+    .ok_or_else(|| anyhow::anyhow!("the end of the racetrack is not reachable"))
+}
 
-            if xv + 1 < grid.len() && !grid[yv][xv + 1].is_wall() && !grid[yv][xv + 1].visited {
-                grid[yv][xv + 1].visited = true;
-                grid[yv][xv + 1].ptr = (xv, yv);
-                queue.push_back((xv + 1, yv));
-            }
+/// Track from the start to the end, without cheating.
+pub struct Racetrack {
+    track: Vec<Point>,
+    /// Time at which every cell of the track is reached.
+    times: Grid<Option<usize>>,
+}
 
-            if yv + 1 < grid.len() && !grid[yv + 1][xv].is_wall() && !grid[yv + 1][xv].visited {
-                grid[yv + 1][xv].visited = true;
-                grid[yv + 1][xv].ptr = (xv, yv);
-                queue.push_back((xv, yv + 1));
-            }
+fn parse<I>(lines: I) -> anyhow::Result<Racetrack>
+where
+    I: Iterator<Item = String>,
+{
+    let grid = Grid::from_lines(lines)?.map(|c| Cell::new((*c).into()));
 
-            if yv > 0 && !grid[yv - 1][xv].is_wall() && !grid[yv - 1][xv].visited {
-                grid[yv - 1][xv].visited = true;
-                grid[yv - 1][xv].ptr = (xv, yv);
-                queue.push_back((xv, yv - 1));
-            }
-        }
+    let track = normal_track(&grid)?;
+
+    let mut times = grid.map(|_| None);
+    for (time, point) in track.iter().enumerate() {
+        times[*point] = Some(time);
     }
 
-    unimplemented!()
+    Ok(Racetrack { track, times })
 }
 
-fn cheat(
-    grid: &mut Vec<Vec<Cell>>,
-    possible: &mut HashSet<((usize, usize), (usize, usize))>,
-    pos: (usize, usize),
-    end: (usize, usize),
-    cheat: bool,
-    current: usize,
-    max: usize,
-) -> usize {
-    if current == max {
-        0
-    } else if pos == end {
-        return max - current;
-    } else {
-        if pos.0 > 1 {
-            if grid[pos.1][pos.0 - 1].is_wall()
-                && !possible.contains(&((pos.0, pos.1), (pos.0, pos.1 - 1)))
-            {}
+/// Number of cheats of at most `max_cheat` picoseconds saving at least `picosecs` picoseconds.
+fn count_cheats(racetrack: &Racetrack, max_cheat: i64, picosecs: usize) -> anyhow::Result<usize> {
+    let Racetrack { track, times } = racetrack;
+
+    let mut res = 0;
+    for (time, point) in track.iter().enumerate() {
+        for dy in -max_cheat..=max_cheat {
+            let max_dx = max_cheat - dy.abs();
+            for dx in -max_dx..=max_dx {
+                let end = *point + Point::new(dx, dy);
+                let Some(Some(end_time)) = times.get(end) else {
+                    continue;
+                };
+
+                let cheat = (dx.abs() + dy.abs()) as usize;
+                if *end_time >= time + cheat + picosecs {
+                    res += 1;
+                }
+            }
         }
-
-        0
     }
-}
 
-fn part_one<I>(mut lines: I, picosecs: usize) -> anyhow::Result<usize>
-where
-    I: Iterator<Item = String>,
-{
-    let mut l = lines
-        .map(|l| l.chars().map(|c| Cell::new(c.into())).collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    let s = normal_track(&mut l);
-
-    println!("f : {}", s);
+    Ok(res)
+}
 
-    unimplemented!()
+/// Number of 2 picoseconds cheats saving at least `picosecs` picoseconds.
+pub fn part_one(racetrack: &Racetrack, picosecs: usize) -> anyhow::Result<usize> {
+    count_cheats(racetrack, 2, picosecs)
 }
 
-fn part_two<I>(mut lines: I) -> anyhow::Result<usize>
-where
-    I: Iterator<Item = String>,
-{
-    unimplemented!()
+/// Number of 20 picoseconds cheats saving at least `picosecs` picoseconds.
+pub fn part_two(racetrack: &Racetrack, picosecs: usize) -> anyhow::Result<usize> {
+    count_cheats(racetrack, 20, picosecs)
 }
 
 #[cfg(test)]
@@ -208,8 +165,9 @@ mod test {
             File::open("./inputs/day20-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()), 2)
-            .expect("failed to run part_one");
+        let input =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let res = super::part_one(&input, 2).expect("failed to run part_one");
 
         assert_eq!(14 + 14 + 2 + 4 + 2 + 3 + 1 + 1 + 1 + 1 + 1, res);
     }
@@ -220,9 +178,14 @@ mod test {
             File::open("./inputs/day20-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_two(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_two");
+        let input =
+            super::parse(input_file.lines().map(|l| l.unwrap())).expect("failed to parse input");
+        let res = super::part_two(&input, 50).expect("failed to run part_two");
 
-        assert_eq!(16, res);
+        // The puzzle's count of cheats saving at least 50 picoseconds.
+        assert_eq!(
+            32 + 31 + 29 + 39 + 25 + 23 + 20 + 19 + 12 + 14 + 12 + 22 + 4 + 3,
+            res
+        );
     }
 }
@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+
+use crate::{error::ParseError, grid::Point, solution::Solution};
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    const TITLE: &'static str = "Keypad Conundrum";
+
+    type Input = Vec<Code>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse<I>(lines: I) -> anyhow::Result<Vec<Code>>
+    where
+        I: Iterator<Item = String>,
+    {
+        Ok(parse_codes(lines)?)
+    }
+
+    fn part_one(codes: &Vec<Code>) -> anyhow::Result<u64> {
+        part::<2>(codes)
+    }
+
+    fn part_two(codes: &Vec<Code>) -> anyhow::Result<u64> {
+        part::<25>(codes)
+    }
+}
+
+/// Position of `key` on the numeric keypad, with the gap at `(0, 3)`.
+fn numeric_position(key: char) -> Point {
+    match key {
+        '7' => Point::new(0, 0),
+        '8' => Point::new(1, 0),
+        '9' => Point::new(2, 0),
+        '4' => Point::new(0, 1),
+        '5' => Point::new(1, 1),
+        '6' => Point::new(2, 1),
+        '1' => Point::new(0, 2),
+        '2' => Point::new(1, 2),
+        '3' => Point::new(2, 2),
+        '0' => Point::new(1, 3),
+        'A' => Point::new(2, 3),
+        _ => unreachable!("codes are validated when parsed"),
+    }
+}
+
+/// Position of `key` on a directional keypad, with the gap at `(0, 0)`.
+fn directional_position(key: char) -> Point {
+    match key {
+        '^' => Point::new(1, 0),
+        'A' => Point::new(2, 0),
+        '<' => Point::new(0, 1),
+        'v' => Point::new(1, 1),
+        '>' => Point::new(2, 1),
+        _ => unreachable!("only arrows and `A` are typed on directional keypads"),
+    }
+}
+
+/// Presses on the keypad of the next depth needed to type every key of `keys` at `depth`,
+/// the arm starting on `A`.
+fn sequence<const N: usize>(
+    keys: &[char],
+    depth: usize,
+    cache: &mut HashMap<(char, char, usize), u64>,
+) -> u64 {
+    let mut from = 'A';
+    keys.iter()
+        .map(|&to| {
+            let presses = presses::<N>(from, to, depth, cache);
+            from = to;
+            presses
+        })
+        .sum()
+}
+
+/// Number of keys you press to move the arm of the keypad at `depth` from `from` to `to` and
+/// press it.
+///
+/// Depth 0 is the numeric keypad, depths 1 to `N` are the directional keypads used by robots and
+/// depth `N + 1` is the one you type on. Results are memoised in `cache`, keyed on
+/// `(from, to, depth)`.
+pub fn presses<const N: usize>(
+    from: char,
+    to: char,
+    depth: usize,
+    cache: &mut HashMap<(char, char, usize), u64>,
+) -> u64 {
+    if depth > N {
+        1
+    } else if cache.contains_key(&(from, to, depth)) {
+        cache[&(from, to, depth)]
+    } else {
+        let (start, end, gap) = if depth == 0 {
+            (
+                numeric_position(from),
+                numeric_position(to),
+                Point::new(0, 3),
+            )
+        } else {
+            (
+                directional_position(from),
+                directional_position(to),
+                Point::new(0, 0),
+            )
+        };
+
+        let delta = end - start;
+        let (left_right, up_down) = (
+            if delta.x < 0 { '<' } else { '>' },
+            if delta.y < 0 { '^' } else { 'v' },
+        );
+        let horizontal = vec![left_right; delta.x.unsigned_abs() as usize];
+        let vertical = vec![up_down; delta.y.unsigned_abs() as usize];
+
+        // Moving along one axis then the other is always at least as cheap as alternating, as
+        // long as the corner is not over the gap.
+        let mut candidates = Vec::with_capacity(2);
+        if Point::new(end.x, start.y) != gap {
+            candidates.push([horizontal.as_slice(), &vertical, &['A']].concat());
+        }
+        if Point::new(start.x, end.y) != gap {
+            candidates.push([vertical.as_slice(), &horizontal, &['A']].concat());
+        }
+
+        let nbr = candidates
+            .iter()
+            .map(|keys| sequence::<N>(keys, depth + 1, cache))
+            .min()
+            .unwrap();
+        cache.insert((from, to, depth), nbr);
+        nbr
+    }
+}
+
+/// Code to type on the numeric keypad.
+pub struct Code {
+    keys: Vec<char>,
+    /// Numeric part of the code.
+    value: u64,
+}
+
+fn parse_codes<I>(lines: I) -> Result<Vec<Code>, ParseError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut codes = Vec::new();
+    for (index, line) in lines.enumerate().filter(|(_, l)| !l.is_empty()) {
+        let keys = line.chars().collect::<Vec<_>>();
+        if let Some(column) = keys.iter().position(|c| !c.is_ascii_digit() && *c != 'A') {
+            return Err(ParseError::new(
+                Day21::DAY,
+                index,
+                column,
+                keys[column],
+                "a digit or `A`",
+            ));
+        }
+
+        let value = line
+            .trim_end_matches('A')
+            .parse::<u64>()
+            .map_err(|_| ParseError::new(Day21::DAY, index, 0, line.as_str(), "a numeric code"))?;
+
+        codes.push(Code { keys, value });
+    }
+
+    Ok(codes)
+}
+
+/// Sum of the complexities of the codes, typed through `N` robots using directional keypads.
+pub fn part<const N: usize>(codes: &[Code]) -> anyhow::Result<u64> {
+    let mut cache = HashMap::new();
+
+    let res = codes
+        .iter()
+        .map(|code| sequence::<N>(&code.keys, 0, &mut cache) * code.value)
+        .sum();
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::HashMap,
+        fs::File,
+        io::{BufRead, BufReader},
+    };
+
+    #[test]
+    fn part_one() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day21-test.txt").expect("failed to open input file"),
+        );
+
+        let input = super::parse_codes(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part::<2>(&input).expect("failed to run part_one");
+
+        assert_eq!(126384, res);
+    }
+
+    #[test]
+    fn sequence_length() {
+        let mut cache = HashMap::new();
+        let keys = ['0', '2', '9', 'A'];
+
+        assert_eq!(12, super::sequence::<0>(&keys, 0, &mut cache));
+        cache.clear();
+        assert_eq!(28, super::sequence::<1>(&keys, 0, &mut cache));
+        cache.clear();
+        assert_eq!(68, super::sequence::<2>(&keys, 0, &mut cache));
+    }
+
+    #[test]
+    fn part_two() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day21-test.txt").expect("failed to open input file"),
+        );
+
+        let input = super::parse_codes(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part::<25>(&input).expect("failed to run part_two");
+
+        assert_eq!(154115708116294, res);
+    }
+}
@@ -1,35 +1,35 @@
 use std::{
     collections::{HashMap, HashSet},
-    env::var,
-    fs::File,
     hash::{DefaultHasher, Hash, Hasher},
-    io::{BufRead, BufReader},
-    iter::Enumerate,
     ops::{AddAssign, BitXor, Rem},
 };
 
-pub fn run_day() -> anyhow::Result<()> {
-    println!("=== DAY 22 ===");
+use crate::{error::ParseError, solution::Solution};
 
-    let res_part_one = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day22.txt").expect("failed to open input file"));
+pub struct Day22;
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_one(lines)?
-    };
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+    const TITLE: &'static str = "Monkey Market";
 
-    println!("part one : {}", res_part_one);
+    type Input = Vec<u64>;
+    type PartOne = u64;
+    type PartTwo = i64;
 
-    let res_part_two = {
-        let input_file =
-            BufReader::new(File::open("./inputs/day22.txt").expect("failed to open input file"));
+    fn parse<I>(lines: I) -> anyhow::Result<Vec<u64>>
+    where
+        I: Iterator<Item = String>,
+    {
+        Ok(parse_secrets(lines)?)
+    }
 
-        let lines = input_file.lines().map(|l| l.unwrap());
-        part_two(lines)?
-    };
-    println!("part two : {:?}", res_part_two);
-    Ok(())
+    fn part_one(secrets: &Vec<u64>) -> anyhow::Result<u64> {
+        part_one(secrets)
+    }
+
+    fn part_two(secrets: &Vec<u64>) -> anyhow::Result<i64> {
+        part_two(secrets)
+    }
 }
 
 #[inline]
@@ -48,22 +48,36 @@ where
     v % (16777216.into())
 }
 
-fn part_one<I>(lines: I) -> anyhow::Result<u64>
+/// Initial secret number of every buyer, one per line.
+fn parse_secrets<I>(lines: I) -> Result<Vec<u64>, ParseError>
 where
     I: Iterator<Item = String>,
 {
-    Ok(lines
-        .map(|l| {
-            l.parse::<u64>()
-                .map(|mut v| {
-                    for _ in 0..2000 {
-                        v = prune(mix(v, v * 64));
-                        v = prune(mix(v, v / 32));
-                        v = prune(mix(v, v * 2048));
-                    }
-                    v
+    lines
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| {
+            line.parse::<u64>()
+                .ok()
+                .filter(|v| *v < 16777216)
+                .ok_or_else(|| {
+                    ParseError::new(Day22::DAY, index, 0, line.as_str(), "a 24-bit number")
                 })
-                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn part_one(secrets: &[u64]) -> anyhow::Result<u64> {
+    Ok(secrets
+        .iter()
+        .map(|v| {
+            let mut v = *v;
+            for _ in 0..2000 {
+                v = prune(mix(v, v * 64));
+                v = prune(mix(v, v / 32));
+                v = prune(mix(v, v * 2048));
+            }
+            v
         })
         .sum())
 }
@@ -76,31 +90,29 @@ fn calculate_hash<T: Hash + ?Sized>(t: &T) -> u64 {
 }
 
 // 1538 to low
-fn part_two<I>(lines: I) -> anyhow::Result<i64>
-where
-    I: Iterator<Item = String>,
-{
-    let sequences = lines
-        .map(|l| {
+fn part_two(secrets: &[u64]) -> anyhow::Result<i64> {
+    let sequences = secrets
+        .iter()
+        .map(|v| {
+            let v = *v;
             let mut r = (Vec::new(), Vec::new());
-            if let Ok(mut v) = l.parse::<i64>() {
-                let mut prec = v % 10;
-                r.0.push(prec);
-                r.1.push(prec);
+            let mut v = v as i64;
+            let mut prec = v % 10;
+            r.0.push(prec);
+            r.1.push(prec);
 
-                for _ in 0..2000 {
-                    v = prune(mix(v, v * 64));
-                    v = prune(mix(v, v / 32));
-                    v = prune(mix(v, v * 2048));
+            for _ in 0..2000 {
+                v = prune(mix(v, v * 64));
+                v = prune(mix(v, v / 32));
+                v = prune(mix(v, v * 2048));
 
-                    let c = v % 10;
+                let c = v % 10;
 
-                    let diff = c - prec;
+                let diff = c - prec;
 
-                    r.0.push(c);
-                    r.1.push(diff);
-                    prec = c;
-                }
+                r.0.push(c);
+                r.1.push(diff);
+                prec = c;
             }
 
             r
@@ -125,7 +137,7 @@ where
         }
     }
 
-    Ok(cache.into_iter().map(|(_, v)| v).max().unwrap())
+    Ok(cache.into_values().max().unwrap_or(0))
 }
 
 #[cfg(test)]
@@ -141,8 +153,9 @@ mod test {
             File::open("./inputs/day22-test.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_one(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_one");
+        let input = super::parse_secrets(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
 
         assert_eq!(37327623, res);
     }
@@ -153,8 +166,9 @@ mod test {
             File::open("./inputs/day22-test2.txt").expect("failed to open input file"),
         );
 
-        let res = super::part_two(input_file.lines().map(|l| l.unwrap()))
-            .expect("failed to run part_two");
+        let input = super::parse_secrets(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_two");
 
         assert_eq!(23, res);
     }
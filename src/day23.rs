@@ -0,0 +1,97 @@
+use crate::{error::ParseError, graph::Graph, solution::Solution};
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    const TITLE: &'static str = "LAN Party";
+
+    type Input = Graph<String>;
+    type PartOne = usize;
+    type PartTwo = String;
+
+    fn parse<I>(lines: I) -> anyhow::Result<Graph<String>>
+    where
+        I: Iterator<Item = String>,
+    {
+        Ok(parse_network(lines)?)
+    }
+
+    fn part_one(network: &Graph<String>) -> anyhow::Result<usize> {
+        part_one(network)
+    }
+
+    fn part_two(network: &Graph<String>) -> anyhow::Result<String> {
+        part_two(network)
+    }
+}
+
+fn is_computer_name(name: &str) -> bool {
+    name.len() == 2 && name.bytes().all(|b| b.is_ascii_lowercase())
+}
+
+fn parse_network<I>(lines: I) -> Result<Graph<String>, ParseError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut network = Graph::new();
+
+    for (index, line) in lines.enumerate().filter(|(_, l)| !l.is_empty()) {
+        let (a, b) = line
+            .split_once('-')
+            .filter(|(a, b)| is_computer_name(a) && is_computer_name(b))
+            .ok_or_else(|| ParseError::new(Day23::DAY, index, 0, line.as_str(), "`aa-bb`"))?;
+
+        network.add_edge(a.to_string(), b.to_string());
+    }
+
+    Ok(network)
+}
+
+fn part_one(network: &Graph<String>) -> anyhow::Result<usize> {
+    let res = network
+        .triangles()
+        .iter()
+        .filter(|triangle| triangle.iter().any(|name| name.starts_with('t')))
+        .count();
+
+    Ok(res)
+}
+
+fn part_two(network: &Graph<String>) -> anyhow::Result<String> {
+    Ok(network.max_clique().join(","))
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fs::File,
+        io::{BufRead, BufReader},
+    };
+
+    #[test]
+    fn part_one() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day23-test.txt").expect("failed to open input file"),
+        );
+
+        let input = super::parse_network(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
+
+        assert_eq!(7, res);
+    }
+
+    #[test]
+    fn part_two() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day23-test.txt").expect("failed to open input file"),
+        );
+
+        let input = super::parse_network(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_two(&input).expect("failed to run part_two");
+
+        assert_eq!("co,de,ka,ta", res);
+    }
+}
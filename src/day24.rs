@@ -0,0 +1,543 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Write},
+};
+
+use crate::{error::ParseError, solution::Solution};
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    const TITLE: &'static str = "Crossed Wires";
+
+    type Input = Circuit;
+    type PartOne = u64;
+    type PartTwo = String;
+
+    fn parse<I>(lines: I) -> anyhow::Result<Circuit>
+    where
+        I: Iterator<Item = String>,
+    {
+        Ok(parse_circuit(lines)?)
+    }
+
+    fn part_one(circuit: &Circuit) -> anyhow::Result<u64> {
+        part_one(circuit)
+    }
+
+    fn part_two(circuit: &Circuit) -> anyhow::Result<String> {
+        part_two(circuit)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    And,
+    Or,
+    Xor,
+}
+
+impl Operation {
+    fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Operation::And => a & b,
+            Operation::Or => a | b,
+            Operation::Xor => a ^ b,
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Operation::And => "AND",
+            Operation::Or => "OR",
+            Operation::Xor => "XOR",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Gate {
+    a: String,
+    operation: Operation,
+    b: String,
+    output: String,
+}
+
+impl Gate {
+    fn has_input(&self, wire: &str) -> bool {
+        self.a == wire || self.b == wire
+    }
+
+    /// Whether the gate is fed by input wires `x..` and `y..`.
+    fn is_first_layer(&self) -> bool {
+        [&self.a, &self.b]
+            .iter()
+            .all(|w| w.starts_with('x') || w.starts_with('y'))
+    }
+}
+
+/// Wires with their initial values and the gates connecting them.
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    inputs: HashMap<String, bool>,
+    gates: Vec<Gate>,
+}
+
+impl Circuit {
+    /// Number represented by the `z..` wires, wire `z00` being the least significant bit, once
+    /// the `inputs` wires are set. `None` if the circuit loops on itself or misses a wire.
+    fn output(&self, inputs: &HashMap<String, bool>) -> Option<u64> {
+        let drivers = self
+            .gates
+            .iter()
+            .map(|g| (g.output.as_str(), g))
+            .collect::<HashMap<_, _>>();
+
+        let mut zs = drivers
+            .keys()
+            .filter(|w| w.starts_with('z'))
+            .copied()
+            .collect::<Vec<_>>();
+        zs.sort_unstable();
+
+        let mut values = HashMap::new();
+        let mut res = 0;
+        for (bit, z) in zs.into_iter().enumerate() {
+            if wire_value(z, inputs, &drivers, &mut values)? {
+                res |= 1 << bit;
+            }
+        }
+
+        Some(res)
+    }
+
+    /// Number of bits of each operand of the adder, given by the `x..` wires.
+    fn bits(&self) -> usize {
+        self.inputs.keys().filter(|w| w.starts_with('x')).count()
+    }
+
+    /// Output of the circuit with `x` and `y` as operands.
+    fn add(&self, x: u64, y: u64) -> Option<u64> {
+        let inputs = (0..self.bits())
+            .flat_map(|bit| {
+                [
+                    (format!("x{:02}", bit), x >> bit & 1 == 1),
+                    (format!("y{:02}", bit), y >> bit & 1 == 1),
+                ]
+            })
+            .collect();
+
+        self.output(&inputs)
+    }
+
+    /// Whether the circuit adds its operands, checked on every bit with and without carries.
+    fn is_adder(&self) -> bool {
+        let bits = self.bits();
+        let all = (1 << bits) - 1;
+
+        (0..bits)
+            .flat_map(|bit| {
+                let n = 1 << bit;
+                [(n, 0), (0, n), (n, n), (all, n), (all ^ n, n)]
+            })
+            .all(|(x, y)| self.add(x, y) == Some(x + y))
+    }
+
+    /// Same circuit, with the outputs of the gates driving each pair of wires exchanged.
+    fn swapped(&self, pairs: &[(&str, &str)]) -> Self {
+        let mut circuit = self.clone();
+
+        for gate in &mut circuit.gates {
+            for (a, b) in pairs {
+                if gate.output == *a {
+                    gate.output = b.to_string();
+                } else if gate.output == *b {
+                    gate.output = a.to_string();
+                }
+            }
+        }
+
+        circuit
+    }
+
+    /// Wires that break the structure of a ripple-carry adder.
+    ///
+    /// Every output bit except the last one must come from a XOR, the last one being the final
+    /// carry from an OR. XOR gates of the second layer only output bits, XOR gates of the first
+    /// layer feed the second layer and AND gates feed the carry ORs. The half adder of the first
+    /// bit has no incoming carry and is not checked.
+    fn misplaced_wires(&self) -> Vec<&str> {
+        let last = format!("z{:02}", self.bits());
+        let feeds = |wire: &str, operation| {
+            self.gates
+                .iter()
+                .any(|g| g.operation == operation && g.has_input(wire))
+        };
+
+        let mut wires = self
+            .gates
+            .iter()
+            .filter(|g| {
+                let output = g.output.as_str();
+                let first_bit = g.has_input("x00");
+
+                if output.starts_with('z') {
+                    let expected = if output == last {
+                        Operation::Or
+                    } else {
+                        Operation::Xor
+                    };
+                    g.operation != expected
+                } else {
+                    match g.operation {
+                        Operation::Xor if !g.is_first_layer() => true,
+                        Operation::Xor => !feeds(output, Operation::Xor),
+                        Operation::And => !first_bit && !feeds(output, Operation::Or),
+                        Operation::Or => false,
+                    }
+                }
+            })
+            .map(|g| g.output.as_str())
+            .collect::<Vec<_>>();
+
+        wires.sort_unstable();
+        wires
+    }
+
+    /// Pairs of wires whose outputs were swapped, making the circuit an adder once exchanged
+    /// back.
+    ///
+    /// Candidates come from [`Circuit::misplaced_wires`], and are paired by trying every
+    /// matching until the circuit adds correctly.
+    pub fn find_swaps(&self) -> anyhow::Result<Vec<(String, String)>> {
+        let candidates = self.misplaced_wires();
+        if !candidates.len().is_multiple_of(2) {
+            anyhow::bail!(
+                "found an odd number of misplaced wires: {}",
+                candidates.join(",")
+            );
+        }
+
+        let pairs = self.pair_up(&candidates, &mut Vec::new()).ok_or_else(|| {
+            anyhow::anyhow!(
+                "no swaps of {} make the circuit an adder",
+                candidates.join(",")
+            )
+        })?;
+
+        Ok(pairs
+            .into_iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect())
+    }
+
+    fn pair_up<'a>(
+        &self,
+        remaining: &[&'a str],
+        pairs: &mut Vec<(&'a str, &'a str)>,
+    ) -> Option<Vec<(&'a str, &'a str)>> {
+        let Some((first, rest)) = remaining.split_first() else {
+            return self.swapped(pairs).is_adder().then(|| pairs.clone());
+        };
+
+        (0..rest.len()).find_map(|i| {
+            let mut others = rest.to_vec();
+            let second = others.remove(i);
+
+            pairs.push((first, second));
+            let res = self.pair_up(&others, pairs);
+            pairs.pop();
+            res
+        })
+    }
+
+    /// Circuit in the Graphviz format, with a node per wire and per gate.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph circuit {\n    rankdir=LR;\n");
+
+        let mut inputs = self.inputs.keys().collect::<Vec<_>>();
+        inputs.sort_unstable();
+        for wire in inputs {
+            writeln!(dot, "    {} [shape=box];", wire).unwrap();
+        }
+
+        let outputs = self
+            .gates
+            .iter()
+            .map(|g| g.output.as_str())
+            .filter(|w| w.starts_with('z'))
+            .collect::<HashSet<_>>();
+        let mut outputs = outputs.into_iter().collect::<Vec<_>>();
+        outputs.sort_unstable();
+        for wire in outputs {
+            writeln!(dot, "    {} [shape=doublecircle];", wire).unwrap();
+        }
+
+        for (i, gate) in self.gates.iter().enumerate() {
+            writeln!(dot, "    gate{} [label=\"{}\"];", i, gate.operation).unwrap();
+            writeln!(dot, "    {} -> gate{};", gate.a, i).unwrap();
+            writeln!(dot, "    {} -> gate{};", gate.b, i).unwrap();
+            writeln!(dot, "    gate{} -> {};", i, gate.output).unwrap();
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Value of `wire`, computing the gates it depends on and memoising them in `values`.
+///
+/// A wire whose value is being computed is marked `None`, reaching it again means the circuit
+/// has a loop.
+fn wire_value<'a>(
+    wire: &'a str,
+    inputs: &HashMap<String, bool>,
+    drivers: &HashMap<&'a str, &'a Gate>,
+    values: &mut HashMap<&'a str, Option<bool>>,
+) -> Option<bool> {
+    if let Some(value) = inputs.get(wire) {
+        return Some(*value);
+    }
+
+    match values.get(wire) {
+        Some(value) => return *value,
+        None => values.insert(wire, None),
+    };
+
+    let gate = drivers.get(wire)?;
+    let a = wire_value(&gate.a, inputs, drivers, values)?;
+    let b = wire_value(&gate.b, inputs, drivers, values)?;
+    let value = gate.operation.apply(a, b);
+
+    values.insert(wire, Some(value));
+    Some(value)
+}
+
+/// Circuit described by the puzzle input, initial wire values first.
+pub fn parse_circuit<I>(lines: I) -> Result<Circuit, ParseError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut lines = lines.enumerate();
+
+    let mut inputs = HashMap::new();
+    for (index, line) in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+
+        let (wire, value) = line
+            .split_once(": ")
+            .ok_or_else(|| ParseError::new(Day24::DAY, index, 0, line.as_str(), "`wire: 0|1`"))?;
+        let value = match value {
+            "0" => false,
+            "1" => true,
+            _ => {
+                return Err(ParseError::new(
+                    Day24::DAY,
+                    index,
+                    wire.len() + 2,
+                    value,
+                    "`0` or `1`",
+                ))
+            }
+        };
+        inputs.insert(wire.to_string(), value);
+    }
+
+    let mut gates = Vec::new();
+    for (index, line) in lines.filter(|(_, l)| !l.is_empty()) {
+        let parts = line.split(' ').collect::<Vec<_>>();
+        let [a, operation, b, "->", output] = parts[..] else {
+            return Err(ParseError::new(
+                Day24::DAY,
+                index,
+                0,
+                line.as_str(),
+                "`a OP b -> c`",
+            ));
+        };
+
+        let operation = match operation {
+            "AND" => Operation::And,
+            "OR" => Operation::Or,
+            "XOR" => Operation::Xor,
+            _ => {
+                return Err(ParseError::new(
+                    Day24::DAY,
+                    index,
+                    a.len() + 1,
+                    operation,
+                    "`AND`, `OR` or `XOR`",
+                ))
+            }
+        };
+
+        gates.push(Gate {
+            a: a.to_string(),
+            operation,
+            b: b.to_string(),
+            output: output.to_string(),
+        });
+    }
+
+    Ok(Circuit { inputs, gates })
+}
+
+fn part_one(circuit: &Circuit) -> anyhow::Result<u64> {
+    circuit
+        .output(&circuit.inputs)
+        .ok_or_else(|| anyhow::anyhow!("the circuit has a loop or an unconnected wire"))
+}
+
+fn part_two(circuit: &Circuit) -> anyhow::Result<String> {
+    let mut wires = circuit
+        .find_swaps()?
+        .into_iter()
+        .flat_map(|(a, b)| [a, b])
+        .collect::<Vec<_>>();
+    wires.sort_unstable();
+
+    Ok(wires.join(","))
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fs::File,
+        io::{BufRead, BufReader},
+    };
+
+    /// Ripple-carry adder of `bits`-bit operands, with the outputs of each pair of wires
+    /// swapped.
+    fn ripple_carry_adder(bits: usize, swaps: &[(&str, &str)]) -> Vec<String> {
+        let mut lines = Vec::new();
+        for bit in 0..bits {
+            lines.push(format!("x{:02}: 0", bit));
+            lines.push(format!("y{:02}: 0", bit));
+        }
+        lines.push(String::new());
+
+        lines.push("x00 XOR y00 -> z00".to_string());
+        lines.push("x00 AND y00 -> c00".to_string());
+        for bit in 1..bits {
+            let carry = if bit == bits - 1 {
+                format!("z{:02}", bits)
+            } else {
+                format!("c{:02}", bit)
+            };
+
+            lines.extend([
+                format!("x{0:02} XOR y{0:02} -> s{0:02}", bit),
+                format!("x{0:02} AND y{0:02} -> a{0:02}", bit),
+                format!("s{0:02} XOR c{1:02} -> z{0:02}", bit, bit - 1),
+                format!("s{0:02} AND c{1:02} -> b{0:02}", bit, bit - 1),
+                format!("a{0:02} OR b{0:02} -> {1}", bit, carry),
+            ]);
+        }
+
+        lines
+            .into_iter()
+            .map(|line| {
+                let Some((gate, output)) = line.split_once(" -> ") else {
+                    return line;
+                };
+                let output = swaps
+                    .iter()
+                    .find_map(|(a, b)| match output {
+                        _ if output == *a => Some(*b),
+                        _ if output == *b => Some(*a),
+                        _ => None,
+                    })
+                    .unwrap_or(output);
+                format!("{} -> {}", gate, output)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn part_one() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day24-test.txt").expect("failed to open input file"),
+        );
+
+        let input = super::parse_circuit(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
+
+        assert_eq!(4, res);
+    }
+
+    #[test]
+    fn part_one_larger() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day24-test2.txt").expect("failed to open input file"),
+        );
+
+        let input = super::parse_circuit(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
+
+        assert_eq!(2024, res);
+    }
+
+    #[test]
+    fn adder() {
+        let circuit = super::parse_circuit(ripple_carry_adder(8, &[]).into_iter()).unwrap();
+
+        assert!(circuit.is_adder());
+        assert_eq!(Some(300), circuit.add(145, 155));
+        assert!(circuit.misplaced_wires().is_empty());
+    }
+
+    #[test]
+    fn part_two() {
+        let swaps = [
+            ("z03", "c03"),
+            ("a07", "s07"),
+            ("z10", "b10"),
+            ("z12", "a12"),
+        ];
+        let lines = ripple_carry_adder(16, &swaps);
+
+        let circuit = super::parse_circuit(lines.into_iter()).unwrap();
+        let mut found = circuit.find_swaps().expect("failed to find swaps");
+        found.sort();
+        assert_eq!(
+            vec![
+                ("a07".to_string(), "s07".to_string()),
+                ("a12".to_string(), "z12".to_string()),
+                ("b10".to_string(), "z10".to_string()),
+                ("c03".to_string(), "z03".to_string()),
+            ],
+            found
+        );
+
+        let res = super::part_two(&circuit).expect("failed to run part_two");
+
+        assert_eq!("a07,a12,b10,c03,s07,z03,z10,z12", res);
+    }
+
+    #[test]
+    fn to_dot() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day24-test.txt").expect("failed to open input file"),
+        );
+
+        let circuit = super::parse_circuit(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse circuit");
+        let dot = circuit.to_dot();
+
+        assert!(dot.starts_with("digraph circuit {\n"));
+        assert!(dot.contains("    x00 [shape=box];\n"));
+        assert!(dot.contains("    z02 [shape=doublecircle];\n"));
+        assert!(dot.contains(
+            "    gate1 [label=\"XOR\"];\n    x01 -> gate1;\n    y01 -> gate1;\n    gate1 -> z01;\n"
+        ));
+        assert!(dot.ends_with("}\n"));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{error::ParseError, grid::Grid, solution::Solution};
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    const TITLE: &'static str = "Code Chronicle";
+    const HAS_PART_TWO: bool = false;
+
+    type Input = Schematics;
+    type PartOne = usize;
+    type PartTwo = &'static str;
+
+    fn parse<I>(lines: I) -> anyhow::Result<Schematics>
+    where
+        I: Iterator<Item = String>,
+    {
+        parse_schematics(lines)
+    }
+
+    fn part_one(schematics: &Schematics) -> anyhow::Result<usize> {
+        part_one(schematics)
+    }
+
+    fn part_two(schematics: &Schematics) -> anyhow::Result<&'static str> {
+        part_two(schematics)
+    }
+}
+
+#[derive(Debug)]
+enum Schematic {
+    Lock(Vec<usize>),
+    Key(Vec<usize>),
+}
+
+/// Locks and keys of the input, as column heights.
+#[derive(Debug, Default)]
+pub struct Schematics {
+    locks: Vec<Vec<usize>>,
+    keys: Vec<Vec<usize>>,
+    /// Space available between a lock and a key.
+    space: usize,
+}
+
+fn parse_schematics<I>(mut lines: I) -> anyhow::Result<Schematics>
+where
+    I: Iterator<Item = String>,
+{
+    let mut locks = Vec::new();
+    let mut keys = Vec::new();
+    let mut space = None;
+
+    let mut offset = 0;
+    loop {
+        let grid = Grid::from_lines(lines.by_ref())?;
+        if grid.height() == 0 {
+            break;
+        }
+
+        let schematic = parse_schematic(&grid, offset)?;
+
+        let available = grid.height().saturating_sub(2);
+        if *space.get_or_insert(available) != available {
+            anyhow::bail!(
+                "schematic starting at line {} has {} rows, expected {}",
+                offset + 1,
+                grid.height(),
+                space.unwrap() + 2
+            );
+        }
+        offset += grid.height() + 1;
+
+        match schematic {
+            Schematic::Lock(heights) => locks.push(heights),
+            Schematic::Key(heights) => keys.push(heights),
+        }
+    }
+
+    Ok(Schematics {
+        locks,
+        keys,
+        space: space.unwrap_or(0),
+    })
+}
+
+/// Parse a schematic whose first row is the `offset`th line of the input.
+fn parse_schematic(grid: &Grid<char>, offset: usize) -> Result<Schematic, ParseError> {
+    let grid = grid.try_map(|p, c| match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => Err(ParseError::new(
+            Day25::DAY,
+            offset + p.y as usize,
+            p.x as usize,
+            *c,
+            "`#` or `.`",
+        )),
+    })?;
+
+    let mut heights = vec![0; grid.width()];
+    for (p, filled) in grid.iter() {
+        if *filled {
+            heights[p.x as usize] += 1;
+        }
+    }
+    // The full row at the top of a lock, or at the bottom of a key, is not a pin.
+    let heights = heights
+        .into_iter()
+        .map(|h: usize| h.saturating_sub(1))
+        .collect();
+
+    if grid.values().take(grid.width()).all(|filled| *filled) {
+        Ok(Schematic::Lock(heights))
+    } else {
+        Ok(Schematic::Key(heights))
+    }
+}
+
+fn part_one(schematics: &Schematics) -> anyhow::Result<usize> {
+    let Schematics { locks, keys, space } = schematics;
+
+    let res = locks
+        .iter()
+        .map(|lock| {
+            keys.iter()
+                .filter(|key| lock.iter().zip(key.iter()).all(|(l, k)| l + k <= *space))
+                .count()
+        })
+        .sum();
+
+    Ok(res)
+}
+
+fn part_two(_schematics: &Schematics) -> anyhow::Result<&'static str> {
+    anyhow::bail!("day 25 has no part two, deliver the chronicle to finish the calendar")
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fs::File,
+        io::{BufRead, BufReader},
+    };
+
+    #[test]
+    fn part_one() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day25-test.txt").expect("failed to open input file"),
+        );
+
+        let input = super::parse_schematics(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse input");
+        let res = super::part_one(&input).expect("failed to run part_one");
+
+        assert_eq!(3, res);
+    }
+
+    #[test]
+    fn heights() {
+        let input_file = BufReader::new(
+            File::open("./inputs/day25-test.txt").expect("failed to open input file"),
+        );
+
+        let schematics = super::parse_schematics(input_file.lines().map(|l| l.unwrap()))
+            .expect("failed to parse schematics");
+
+        assert_eq!(5, schematics.space);
+        assert_eq!(
+            vec![vec![0, 5, 3, 4, 3], vec![1, 2, 0, 5, 3]],
+            schematics.locks
+        );
+        assert_eq!(
+            vec![
+                vec![5, 0, 2, 1, 3],
+                vec![4, 3, 4, 0, 2],
+                vec![3, 0, 2, 0, 1]
+            ],
+            schematics.keys
+        );
+    }
+}
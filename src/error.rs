@@ -0,0 +1,44 @@
+use std::fmt;
+
+/// Error raised when a puzzle input does not have the expected format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// Line of the offending text, starting at 1.
+    pub line: usize,
+    /// Column of the offending text, starting at 1.
+    pub column: usize,
+    pub text: String,
+    /// What was expected instead of `text`.
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Error on the `index`th line (starting at 0) of the input, where `text` starts at the
+    /// character `offset` (starting at 0).
+    pub fn new<T, E>(day: u8, index: usize, offset: usize, text: T, expected: E) -> Self
+    where
+        T: Into<String>,
+        E: Into<String>,
+    {
+        Self {
+            day,
+            line: index + 1,
+            column: offset + 1,
+            text: text.into(),
+            expected: expected.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {:02}, line {}, column {}: expected {}, found `{}`",
+            self.day, self.line, self.column, self.expected, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
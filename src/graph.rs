@@ -0,0 +1,179 @@
+use std::hash::Hash;
+
+use fxhash::{FxHashMap, FxHashSet};
+
+/// Undirected graph without edge weights, stored as adjacency sets.
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    edges: FxHashMap<N, FxHashSet<N>>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Self {
+            edges: FxHashMap::default(),
+        }
+    }
+}
+
+impl<N> Graph<N>
+where
+    N: Clone + Ord + Hash,
+{
+    /// Empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connect `a` and `b`, adding them to the graph if needed.
+    pub fn add_edge(&mut self, a: N, b: N) {
+        self.edges.entry(a.clone()).or_default().insert(b.clone());
+        self.edges.entry(b).or_default().insert(a);
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.edges.keys()
+    }
+
+    /// Nodes connected to `node`, in no particular order.
+    pub fn neighbours(&self, node: &N) -> impl Iterator<Item = &N> {
+        self.edges.get(node).into_iter().flatten()
+    }
+
+    pub fn contains_edge(&self, a: &N, b: &N) -> bool {
+        self.edges.get(a).is_some_and(|n| n.contains(b))
+    }
+
+    /// Every set of three nodes connected to each other, each sorted and listed once.
+    pub fn triangles(&self) -> Vec<[N; 3]> {
+        let mut triangles = Vec::new();
+
+        for (a, neighbours) in &self.edges {
+            for b in neighbours.iter().filter(|b| *b > a) {
+                for c in self.edges[b].iter().filter(|c| *c > b) {
+                    if neighbours.contains(c) {
+                        triangles.push([a.clone(), b.clone(), c.clone()]);
+                    }
+                }
+            }
+        }
+
+        triangles
+    }
+
+    /// Largest set of nodes all connected to each other, sorted.
+    ///
+    /// Uses the Bron–Kerbosch algorithm, pivoting on the node with the most neighbours among
+    /// the candidates to skip the branches that cannot give a maximal clique.
+    pub fn max_clique(&self) -> Vec<N> {
+        let mut best = Vec::new();
+        self.bron_kerbosch(
+            &mut Vec::new(),
+            self.edges.keys().cloned().collect(),
+            FxHashSet::default(),
+            &mut best,
+        );
+
+        best.sort();
+        best
+    }
+
+    fn bron_kerbosch(
+        &self,
+        clique: &mut Vec<N>,
+        mut candidates: FxHashSet<N>,
+        mut excluded: FxHashSet<N>,
+        best: &mut Vec<N>,
+    ) {
+        if candidates.is_empty() {
+            if excluded.is_empty() && clique.len() > best.len() {
+                *best = clique.clone();
+            }
+            return;
+        }
+
+        // A clique too small to beat the best one even with every candidate can be dropped.
+        if clique.len() + candidates.len() <= best.len() {
+            return;
+        }
+
+        let pivot = candidates
+            .union(&excluded)
+            .max_by_key(|n| self.edges[*n].intersection(&candidates).count())
+            .cloned()
+            .unwrap();
+
+        let branches = candidates
+            .difference(&self.edges[&pivot])
+            .cloned()
+            .collect::<Vec<_>>();
+        for node in branches {
+            let neighbours = &self.edges[&node];
+
+            clique.push(node.clone());
+            self.bron_kerbosch(
+                clique,
+                candidates.intersection(neighbours).cloned().collect(),
+                excluded.intersection(neighbours).cloned().collect(),
+                best,
+            );
+            clique.pop();
+
+            candidates.remove(&node);
+            excluded.insert(node);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Graph;
+
+    fn graph(edges: &[(u32, u32)]) -> Graph<u32> {
+        let mut graph = Graph::new();
+        for (a, b) in edges {
+            graph.add_edge(*a, *b);
+        }
+        graph
+    }
+
+    #[test]
+    fn triangles() {
+        // Two triangles sharing the 1-2 edge, and a dangling node.
+        let graph = graph(&[(1, 2), (2, 3), (3, 1), (1, 4), (4, 2), (4, 5)]);
+
+        let mut triangles = graph.triangles();
+        triangles.sort();
+        assert_eq!(vec![[1, 2, 3], [1, 2, 4]], triangles);
+        assert!(graph.contains_edge(&5, &4));
+        assert!(!graph.contains_edge(&5, &1));
+    }
+
+    #[test]
+    fn max_clique() {
+        // A 4-clique and a triangle, joined by a single edge.
+        let graph = graph(&[
+            (1, 2),
+            (1, 3),
+            (1, 4),
+            (2, 3),
+            (2, 4),
+            (3, 4),
+            (4, 5),
+            (5, 6),
+            (6, 7),
+            (7, 5),
+        ]);
+
+        assert_eq!(vec![1, 2, 3, 4], graph.max_clique());
+        assert!(Graph::<u32>::new().max_clique().is_empty());
+    }
+}
@@ -0,0 +1,426 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+/// Position on a [`Grid`], `y` growing downwards.
+///
+/// Coordinates are signed so that points just outside of the grid can be represented, `get`
+/// returning `None` for them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    /// Offsets of the 8 points surrounding a point, diagonals included.
+    pub const ADJACENT: [Point; 8] = [
+        Point::new(-1, -1),
+        Point::new(0, -1),
+        Point::new(1, -1),
+        Point::new(-1, 0),
+        Point::new(1, 0),
+        Point::new(-1, 1),
+        Point::new(0, 1),
+        Point::new(1, 1),
+    ];
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Direction) -> Point {
+        self + rhs.offset()
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i64) -> Point {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+/// One of the four cardinal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Every direction, clockwise from `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub const fn offset(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub const fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub const fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub const fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+}
+
+/// Rectangular grid, stored row by row in a single `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid of `width`x`height` cells, all set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Build a grid from its rows, which must all have the same length.
+    pub fn from_rows<R>(rows: R) -> anyhow::Result<Self>
+    where
+        R: IntoIterator<Item = Vec<T>>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for row in rows {
+            let expected = *width.get_or_insert(row.len());
+            if row.len() != expected {
+                anyhow::bail!(
+                    "row {} has {} cells, expected {}",
+                    height + 1,
+                    row.len(),
+                    expected
+                );
+            }
+            cells.extend(row);
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (0..self.width as i64).contains(&point.x) && (0..self.height as i64).contains(&point.y)
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        if self.contains(point) {
+            Some(point.y as usize * self.width + point.x as usize)
+        } else {
+            None
+        }
+    }
+
+    fn point_of(&self, index: usize) -> Point {
+        Point::new((index % self.width) as i64, (index / self.width) as i64)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.cells[i])
+    }
+
+    /// Every point of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<T> {
+        let width = self.width as i64;
+        (0..self.height as i64).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.point_of(i), cell))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, cell)| (Point::new((i % width) as i64, (i / width) as i64), cell))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Point of the first cell, row by row, matching `predicate`.
+    pub fn position<P>(&self, predicate: P) -> Option<Point>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| self.point_of(i))
+    }
+
+    /// Neighbours of `point` in the four cardinal directions that are inside the grid.
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = Point> + use<'_, T> {
+        Direction::ALL
+            .into_iter()
+            .map(move |d| point + d)
+            .filter(|p| self.contains(*p))
+    }
+
+    /// Neighbours of `point`, diagonals included, that are inside the grid.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + use<'_, T> {
+        Point::ADJACENT
+            .into_iter()
+            .map(move |o| point + o)
+            .filter(|p| self.contains(*p))
+    }
+
+    /// Points from `start` (excluded) moving by `step` until leaving the grid.
+    pub fn ray(&self, start: Point, step: Point) -> impl Iterator<Item = Point> + use<'_, T> {
+        std::iter::successors(Some(start + step), move |p| Some(*p + step))
+            .take_while(|p| self.contains(*p))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Convert every cell, stopping at the first error.
+    pub fn try_map<U, E, F>(&self, mut f: F) -> Result<Grid<U>, E>
+    where
+        F: FnMut(Point, &T) -> Result<U, E>,
+    {
+        Ok(Grid {
+            width: self.width,
+            height: self.height,
+            cells: self
+                .iter()
+                .map(|(p, c)| f(p, c))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl Grid<char> {
+    /// Parse a grid of characters, stopping at the first empty line.
+    pub fn from_lines<I>(lines: I) -> anyhow::Result<Self>
+    where
+        I: Iterator<Item = String>,
+    {
+        Self::from_rows(
+            lines
+                .take_while(|l| !l.is_empty())
+                .map(|l| l.chars().collect()),
+        )
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_lines(s.lines().map(str::to_owned))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.get(point) {
+            Some(cell) => cell,
+            None => panic!(
+                "point {} is outside of the {}x{} grid",
+                point, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(point) {
+            Some(cell) => cell,
+            None => panic!(
+                "point {} is outside of the {}x{} grid",
+                point, width, height
+            ),
+        }
+    }
+}
+
+impl<T> fmt::Display for Grid<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.cells.chunks(self.width.max(1)).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Direction, Grid, Point};
+
+    #[test]
+    fn parse_and_display() {
+        let grid = "ab\ncd\n\nignored".parse::<Grid<char>>().unwrap();
+
+        assert_eq!((2, 2), (grid.width(), grid.height()));
+        assert_eq!(Some(&'c'), grid.get(Point::new(0, 1)));
+        assert_eq!(None, grid.get(Point::new(2, 0)));
+        assert_eq!("ab\ncd", grid.to_string());
+    }
+
+    #[test]
+    fn ragged_rows() {
+        assert!("abc\nde".parse::<Grid<char>>().is_err());
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(3, 3, 0);
+
+        assert_eq!(2, grid.neighbours(Point::new(0, 0)).count());
+        assert_eq!(4, grid.neighbours(Point::new(1, 1)).count());
+        assert_eq!(3, grid.neighbours8(Point::new(2, 2)).count());
+        assert_eq!(8, grid.neighbours8(Point::new(1, 1)).count());
+    }
+
+    #[test]
+    fn ray() {
+        let grid = Grid::new(4, 3, 0);
+
+        let ray = grid
+            .ray(Point::new(0, 0), Direction::Right.offset())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![Point::new(1, 0), Point::new(2, 0), Point::new(3, 0)],
+            ray
+        );
+        assert_eq!(2, grid.ray(Point::new(0, 0), Point::new(1, 1)).count());
+    }
+}
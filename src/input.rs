@@ -0,0 +1,67 @@
+use std::{
+    convert::Infallible,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use anyhow::Context;
+
+/// Where the puzzle input of a day is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    File(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl Input {
+    /// Default input of a day, `./inputs/dayNN.txt`.
+    pub fn for_day(day: u8) -> Self {
+        Input::File(PathBuf::from(format!("./inputs/day{:02}.txt", day)))
+    }
+
+    /// Read the whole input in memory.
+    ///
+    /// Stdin can only be consumed once, so the result should be kept around when running
+    /// both parts.
+    pub fn read(&self) -> anyhow::Result<String> {
+        match self {
+            Input::File(path) => fs::read_to_string(path)
+                .with_context(|| format!("failed to read input file {}", path.display())),
+            Input::Stdin => {
+                let mut buf = String::new();
+                io::stdin()
+                    .read_to_string(&mut buf)
+                    .context("failed to read input from stdin")?;
+                Ok(buf)
+            }
+            Input::Inline(s) => Ok(s.clone()),
+        }
+    }
+
+    /// Read the input and iterate over its lines, as expected by [`Solution`] parts.
+    ///
+    /// [`Solution`]: crate::Solution
+    pub fn lines(&self) -> anyhow::Result<impl Iterator<Item = String>> {
+        let input = self.read()?;
+        Ok(input
+            .lines()
+            .map(str::to_owned)
+            .collect::<Vec<_>>()
+            .into_iter())
+    }
+}
+
+impl FromStr for Input {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "-" {
+            Ok(Input::Stdin)
+        } else {
+            Ok(Input::File(PathBuf::from(s)))
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Solutions of the Advent of Code 2024 puzzles.
+//!
+//! Every day implements [`Solution`] and is listed in the [`DAYS`] registry, which the `aoc`
+//! binary uses to run, time and verify the solvers:
+//!
+//! ```no_run
+//! use advent_of_code_2024::{solution::get_day, Input, Part};
+//!
+//! let day = get_day(1).unwrap();
+//! let input = Input::for_day(day.number).read()?;
+//! println!("{}", day.run(Part::One, &input)?.value);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! Building blocks shared by several days live in [`grid`], [`graph`] and [`pathfinding`].
+//! Helpers that are useful outside of their day, such as [`day11::expand`] or
+//! [`day13::gaussian_elimination`], are public as well.
+
+pub mod answers;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod error;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod pathfinding;
+pub mod report;
+pub mod selection;
+pub mod solution;
+
+pub use error::ParseError;
+pub use input::Input;
+pub use solution::{Answer, Day, Part, Solution, DAYS};
@@ -1,42 +1,322 @@
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day18;
-mod day19;
-mod day20;
-mod day22;
+use std::{
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+use advent_of_code_2024::{
+    answers::{Answers, Verdict},
+    day06::{self, Edges},
+    day09::{self, Strategy, TraceStep},
+    day17, day24,
+    grid::Point,
+    report::Report,
+    selection::DaySelection,
+    Day, Input, Part,
+};
+use anyhow::{anyhow, bail};
+use clap::{Args, Parser, Subcommand};
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc", about = "Advent of Code 2024 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run the solvers of one or more days.
+    Run {
+        /// Days to run: a single day (`6`), an inclusive range (`1..13`) or `all`.
+        #[arg(default_value = "all")]
+        days: DaySelection,
+
+        /// Only run the given part (`1` or `2`).
+        #[arg(short, long)]
+        part: Option<Part>,
+
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// Print the summary as JSON instead of a table.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check the answers of the real inputs against known-good answers.
+    Verify {
+        /// Days to verify: a single day (`6`), an inclusive range (`1..13`) or `all`.
+        #[arg(default_value = "all")]
+        days: DaySelection,
+
+        /// File containing the known-good answers.
+        #[arg(short, long, value_name = "PATH", default_value = "answers.toml")]
+        answers: PathBuf,
+    },
+    /// Inspect the inner workings of a day's solver.
+    Debug {
+        #[command(subcommand)]
+        tool: Tool,
+    },
+}
+
+#[derive(Debug, Args)]
+struct InputArgs {
+    /// Read the input from this file instead of `./inputs/dayNN.txt`, or from stdin with `-`.
+    #[arg(short, long, value_name = "PATH")]
+    input: Option<Input>,
+
+    /// Use the given text as the input.
+    #[arg(long, value_name = "TEXT", conflicts_with = "input")]
+    inline: Option<String>,
+}
+
+impl InputArgs {
+    /// Input given on the command line, if any.
+    fn get(self) -> Option<Input> {
+        self.input.or(self.inline.map(Input::Inline))
+    }
+
+    /// Input given on the command line, or the default one of `day`.
+    fn or_day(self, day: u8) -> Input {
+        self.get().unwrap_or_else(|| Input::for_day(day))
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Tool {
+    /// Simulate the day 06 guards and count the obstacles making them loop.
+    Patrol {
+        /// Make guards walking past an edge come back from the opposite one.
+        #[arg(long)]
+        wrap: bool,
+
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Print every state of the day 06 guard, until it leaves the map or loops.
+    Route {
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// List the day 06 obstacles making the guard loop, with the length of each loop.
+    Obstacles {
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Draw the route of the day 06 guard on the map.
+    Render {
+        /// Place an extra obstacle on the cell `X,Y` first.
+        #[arg(long, value_name = "X,Y", value_parser = parse_point)]
+        obstacle: Option<Point>,
+
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Print the moves compacting the day 09 disk, and its layout along the way.
+    Trace {
+        /// Move whole files, as in part two, instead of single blocks.
+        #[arg(long)]
+        files: bool,
+
+        /// Print the layout every N moves.
+        #[arg(long, value_name = "N", default_value_t = 1)]
+        every: usize,
+
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Print the day 17 program as readable instructions.
+    Disassemble {
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Print the day 24 circuit in the Graphviz format.
+    Dot {
+        #[command(flatten)]
+        input: InputArgs,
+    },
+}
+
+fn parse_point(s: &str) -> anyhow::Result<Point> {
+    let (x, y) = s
+        .split_once(',')
+        .ok_or_else(|| anyhow!("invalid cell `{}`, expected `X,Y`", s))?;
+    Ok(Point::new(x.trim().parse()?, y.trim().parse()?))
+}
+
+fn run_day(
+    day: &Day,
+    part: Option<Part>,
+    input: &Input,
+    quiet: bool,
+    report: &mut Report,
+) -> anyhow::Result<()> {
+    if !quiet {
+        println!("=== DAY {:02}: {} ===", day.number, day.title);
+    }
+
+    let input = input.read()?;
+
+    let parts = match part {
+        Some(part) => &[part][..],
+        None => day.parts(),
+    };
+
+    for &part in parts {
+        let answer = day.run(part, &input)?;
+        if !quiet {
+            println!(
+                "{} : {} (parse {:.2?}, solve {:.2?})",
+                part, answer.value, answer.parse_time, answer.solve_time
+            );
+        }
+        report.push(day.number, part, &answer);
+    }
+
+    Ok(())
+}
+
+fn verify(days: &[&Day], answers: &Answers) -> anyhow::Result<()> {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for day in days {
+        let input = Input::for_day(day.number).read();
+
+        for &part in day.parts() {
+            let answer = match &input {
+                Ok(input) => day.run(part, input),
+                Err(e) => Err(anyhow!("{:#}", e)),
+            };
+
+            let verdict = answers.check(day.number, part, answer);
+            match verdict {
+                Verdict::Pass => passed += 1,
+                Verdict::Missing { .. } => missing += 1,
+                Verdict::Fail { .. } | Verdict::Error(_) => failed += 1,
+            }
+            println!("day {:02} {} : {}", day.number, part, verdict);
+        }
+    }
+
+    println!();
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+
+    if failed > 0 || missing > 0 {
+        bail!("verification failed");
+    }
+
+    Ok(())
+}
+
+fn debug(tool: Tool) -> anyhow::Result<()> {
+    match tool {
+        Tool::Patrol { wrap, input } => {
+            // Stdin can only be read once, so the lab is parsed once for both answers.
+            let lab = day06::parse(input.or_day(6).lines()?)?;
+            let edges = if wrap { Edges::Wrap } else { Edges::Exit };
+
+            let patrol = day06::simulate(&lab, edges);
+            println!("visited : {}", patrol.visited.len());
+            println!("looped : {}", patrol.looped);
+            println!("loop obstacles : {}", day06::part_two(&lab, edges)?);
+        }
+        Tool::Route { input } => {
+            let route = day06::route(&day06::parse(input.or_day(6).lines()?)?)?;
+            for (x, y, dir) in &route.steps {
+                println!("{},{} {:?}", x, y, dir);
+            }
+            if route.looped {
+                println!("looped");
+            }
+        }
+        Tool::Obstacles { input } => {
+            for obstacle in day06::loop_obstacles(&day06::parse(input.or_day(6).lines()?)?)? {
+                println!("{} : loop of {}", obstacle.position, obstacle.loop_length);
+            }
+        }
+        Tool::Render { obstacle, input } => {
+            println!(
+                "{}",
+                day06::render(&day06::parse(input.or_day(6).lines()?)?, obstacle)?
+            );
+        }
+        Tool::Trace {
+            files,
+            every,
+            input,
+        } => {
+            let disk = day09::parse_disk_map(input.or_day(9).lines()?)?;
+            let strategy = if files {
+                Strategy::Files
+            } else {
+                Strategy::Blocks
+            };
+
+            // Layouts can be huge, so every step is written out as soon as it is made.
+            let mut out = BufWriter::new(io::stdout().lock());
+            let mut written = Ok(());
+            day09::trace(&disk, strategy, every, |step| {
+                if written.is_ok() {
+                    written = match step {
+                        TraceStep::Move(m) => {
+                            writeln!(out, "file {} : {} -> {}", m.id, m.from, m.to)
+                        }
+                        TraceStep::Layout(layout) => writeln!(out, "{}", layout),
+                    };
+                }
+            })?;
+            written.and_then(|_| out.flush())?;
+        }
+        Tool::Disassemble { input } => {
+            let computer = day17::parse_computer(input.or_day(17).lines()?)?;
+            print!("{}", day17::disassemble(&computer.program));
+        }
+        Tool::Dot { input } => {
+            let circuit = day24::parse_circuit(input.or_day(24).lines()?)?;
+            print!("{}", circuit.to_dot());
+        }
+    }
+
+    Ok(())
+}
 
 fn main() -> anyhow::Result<()> {
-    day01::run_day()?;
-    day02::run_day()?;
-    day03::run_day()?;
-    day04::run_day()?;
-    day05::run_day()?;
-    day06::run_day()?;
-    day07::run_day()?;
-    day08::run_day()?;
-    day09::run_day()?;
-    day10::run_day()?;
-    day11::run_day()?;
-    day12::run_day()?;
-    day13::run_day()?;
-    day18::run_day()?;
-    day19::run_day()?;
-    day20::run_day()?;
-    day22::run_day()?;
-
-    // day23::run_day()?;
-    // day24::run_day()?;
-    day25::run_day()?;
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run {
+            days,
+            part,
+            input,
+            json,
+        } => {
+            let days = days.days()?;
+            let input = input.get();
+
+            if input.is_some() && days.len() > 1 {
+                bail!("an explicit input can only be used when running a single day");
+            }
+
+            let mut report = Report::default();
+            for day in days {
+                let input = input.clone().unwrap_or_else(|| Input::for_day(day.number));
+                run_day(day, part, &input, json, &mut report)?;
+            }
+
+            if json {
+                println!("{}", report.to_json()?);
+            } else {
+                println!();
+                println!("{}", report);
+            }
+        }
+        Command::Verify { days, answers } => {
+            let days = days.days()?;
+            let answers = Answers::load(answers)?;
+            verify(&days, &answers)?;
+        }
+        Command::Debug { tool } => debug(tool)?,
+    }
+
     Ok(())
 }
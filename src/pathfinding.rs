@@ -0,0 +1,460 @@
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, VecDeque},
+    hash::Hash,
+};
+
+use fxhash::{FxHashMap, FxHashSet};
+use num_traits::Zero;
+
+/// Shortest path from `start` to the first node matching `is_goal`, every edge costing 1.
+///
+/// The path starts with `start` and ends with the goal.
+pub fn bfs<N, F, I, G>(start: N, mut neighbours: F, mut is_goal: G) -> Option<Vec<N>>
+where
+    N: Copy + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+{
+    let mut parents = FxHashMap::default();
+    parents.insert(start, start);
+
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(build_path(&parents, start, node));
+        }
+
+        for next in neighbours(&node) {
+            if let Entry::Vacant(e) = parents.entry(next) {
+                e.insert(node);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Number of steps from `start` to every reachable node, every edge costing 1.
+pub fn bfs_distances<N, F, I>(start: N, mut neighbours: F) -> FxHashMap<N, usize>
+where
+    N: Copy + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = FxHashMap::default();
+    distances.insert(start, 0);
+
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        for next in neighbours(&node) {
+            if let Entry::Vacant(e) = distances.entry(next) {
+                e.insert(distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Every shortest path from `start` to the nodes matching `is_goal`, every edge costing 1.
+pub fn bfs_all<N, F, I, G>(
+    start: N,
+    mut neighbours: F,
+    is_goal: G,
+) -> Option<ShortestPaths<N, usize>>
+where
+    N: Copy + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+{
+    dijkstra_all(
+        start,
+        |n| neighbours(n).into_iter().map(|next| (next, 1)),
+        is_goal,
+    )
+}
+
+/// Cheapest path from `start` to the first node matching `is_goal`, with its cost.
+///
+/// `neighbours` returns the nodes reachable from a node along with the cost to reach them,
+/// which must not be negative.
+pub fn dijkstra<N, C, F, I, G>(start: N, neighbours: F, is_goal: G) -> Option<(Vec<N>, C)>
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Zero,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    G: FnMut(&N) -> bool,
+{
+    astar(start, neighbours, |_| C::zero(), is_goal)
+}
+
+/// Cost of the cheapest path from `start` to every reachable node.
+pub fn dijkstra_distances<N, C, F, I>(start: N, neighbours: F) -> FxHashMap<N, C>
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Zero,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let search = Search::run(start, neighbours, |_| C::zero(), |_| false, false);
+    search
+        .visits
+        .into_iter()
+        .map(|(node, visit)| (node, visit.cost))
+        .collect()
+}
+
+/// Every cheapest path from `start` to the nodes matching `is_goal`.
+pub fn dijkstra_all<N, C, F, I, G>(
+    start: N,
+    neighbours: F,
+    is_goal: G,
+) -> Option<ShortestPaths<N, C>>
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Zero,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    G: FnMut(&N) -> bool,
+{
+    let search = Search::run(start, neighbours, |_| C::zero(), is_goal, true);
+    let cost = search.cost?;
+
+    Some(ShortestPaths {
+        start,
+        cost,
+        goals: search.goals,
+        parents: search
+            .visits
+            .into_iter()
+            .map(|(node, visit)| (node, visit.parents))
+            .collect(),
+    })
+}
+
+/// Cheapest path from `start` to the first node matching `is_goal`, with its cost, exploring
+/// the nodes with the lowest `cost + heuristic` first.
+///
+/// The heuristic must never overestimate the cost to reach a goal for the path to be the
+/// cheapest one.
+pub fn astar<N, C, F, I, H, G>(
+    start: N,
+    neighbours: F,
+    heuristic: H,
+    is_goal: G,
+) -> Option<(Vec<N>, C)>
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Zero,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+    G: FnMut(&N) -> bool,
+{
+    let search = Search::run(start, neighbours, heuristic, is_goal, false);
+    let cost = search.cost?;
+    let goal = search.goals[0];
+
+    let parents = search
+        .visits
+        .into_iter()
+        .map(|(node, visit)| (node, visit.parents.first().copied().unwrap_or(start)))
+        .collect();
+
+    Some((build_path(&parents, start, goal), cost))
+}
+
+/// Every cheapest path between a start and the goals reached at the lowest cost.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<N, C> {
+    start: N,
+    pub cost: C,
+    pub goals: Vec<N>,
+    parents: FxHashMap<N, Vec<N>>,
+}
+
+impl<N, C> ShortestPaths<N, C>
+where
+    N: Copy + Eq + Hash,
+{
+    /// Nodes that are on at least one of the paths.
+    pub fn nodes(&self) -> FxHashSet<N> {
+        let mut nodes = FxHashSet::default();
+        let mut stack = self.goals.clone();
+
+        while let Some(node) = stack.pop() {
+            if nodes.insert(node) {
+                stack.extend(self.parents.get(&node).into_iter().flatten());
+            }
+        }
+
+        nodes
+    }
+
+    /// Every path, from the start to a goal.
+    ///
+    /// Their number can grow exponentially with the size of the graph, prefer [`nodes`] when
+    /// the paths themselves are not needed.
+    ///
+    /// [`nodes`]: ShortestPaths::nodes
+    pub fn paths(&self) -> Vec<Vec<N>> {
+        let mut paths = Vec::new();
+        for goal in &self.goals {
+            self.collect_paths(&mut vec![*goal], &mut paths);
+        }
+        paths
+    }
+
+    fn collect_paths(&self, current: &mut Vec<N>, paths: &mut Vec<Vec<N>>) {
+        let node = *current.last().unwrap();
+        if node == self.start {
+            paths.push(current.iter().rev().copied().collect());
+            return;
+        }
+
+        for parent in self.parents.get(&node).into_iter().flatten() {
+            current.push(*parent);
+            self.collect_paths(current, paths);
+            current.pop();
+        }
+    }
+}
+
+fn build_path<N>(parents: &FxHashMap<N, N>, start: N, goal: N) -> Vec<N>
+where
+    N: Copy + Eq + Hash,
+{
+    let mut path = vec![goal];
+    let mut node = goal;
+    while node != start {
+        node = parents[&node];
+        path.push(node);
+    }
+    path.reverse();
+    path
+}
+
+struct Visit<N, C> {
+    cost: C,
+    /// Nodes from which this node is reached at `cost`, only the first one unless every path
+    /// is searched.
+    parents: Vec<N>,
+}
+
+/// Entry of the priority queue, the lowest priority being popped first.
+struct State<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for State<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for State<N, C> {}
+
+impl<N, C: Ord> PartialOrd for State<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for State<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+struct Search<N, C> {
+    visits: FxHashMap<N, Visit<N, C>>,
+    goals: Vec<N>,
+    cost: Option<C>,
+}
+
+impl<N, C> Search<N, C>
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Zero,
+{
+    /// Explore the graph from `start` until a goal is reached, or until every goal reachable
+    /// at the lowest cost is if `all` is set.
+    fn run<F, I, H, G>(
+        start: N,
+        mut neighbours: F,
+        mut heuristic: H,
+        mut is_goal: G,
+        all: bool,
+    ) -> Self
+    where
+        F: FnMut(&N) -> I,
+        I: IntoIterator<Item = (N, C)>,
+        H: FnMut(&N) -> C,
+        G: FnMut(&N) -> bool,
+    {
+        let mut visits = FxHashMap::default();
+        visits.insert(
+            start,
+            Visit {
+                cost: C::zero(),
+                parents: Vec::new(),
+            },
+        );
+
+        let mut queue = BinaryHeap::new();
+        queue.push(State {
+            priority: heuristic(&start),
+            cost: C::zero(),
+            node: start,
+        });
+
+        let mut goals = Vec::new();
+        let mut best = None;
+
+        while let Some(State {
+            priority,
+            cost,
+            node,
+        }) = queue.pop()
+        {
+            if best.is_some_and(|best| priority > best) {
+                break;
+            }
+
+            if cost > visits[&node].cost {
+                continue;
+            }
+
+            if is_goal(&node) {
+                best = Some(cost);
+                goals.push(node);
+                if all {
+                    continue;
+                } else {
+                    break;
+                }
+            }
+
+            for (next, step) in neighbours(&node) {
+                let next_cost = cost + step;
+                match visits.entry(next) {
+                    Entry::Vacant(e) => {
+                        e.insert(Visit {
+                            cost: next_cost,
+                            parents: vec![node],
+                        });
+                    }
+                    Entry::Occupied(mut e) => {
+                        let visit = e.get_mut();
+                        if next_cost < visit.cost {
+                            visit.cost = next_cost;
+                            visit.parents = vec![node];
+                        } else {
+                            if all && next_cost == visit.cost && !visit.parents.contains(&node) {
+                                visit.parents.push(node);
+                            }
+                            continue;
+                        }
+                    }
+                }
+
+                queue.push(State {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+
+        Self {
+            visits,
+            goals,
+            cost: best,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grid::{Grid, Point};
+
+    const MAZE: &str = "\
+...
+.#.
+...";
+
+    fn open_neighbours(grid: &Grid<char>, p: Point) -> Vec<Point> {
+        grid.neighbours(p).filter(|n| grid[*n] == '.').collect()
+    }
+
+    #[test]
+    fn bfs() {
+        let grid = MAZE.parse::<Grid<char>>().unwrap();
+        let end = Point::new(2, 2);
+
+        let path = super::bfs(
+            Point::new(0, 0),
+            |p| open_neighbours(&grid, *p),
+            |p| *p == end,
+        )
+        .expect("end should be reachable");
+        assert_eq!(5, path.len());
+        assert_eq!(Some(&end), path.last());
+
+        let distances = super::bfs_distances(Point::new(0, 0), |p| open_neighbours(&grid, *p));
+        assert_eq!(Some(&4), distances.get(&end));
+        assert_eq!(None, distances.get(&Point::new(1, 1)));
+    }
+
+    #[test]
+    fn all_shortest_paths() {
+        let grid = MAZE.parse::<Grid<char>>().unwrap();
+        let end = Point::new(2, 2);
+
+        let paths = super::bfs_all(
+            Point::new(0, 0),
+            |p| open_neighbours(&grid, *p),
+            |p| *p == end,
+        )
+        .expect("end should be reachable");
+
+        assert_eq!(4, paths.cost);
+        assert_eq!(2, paths.paths().len());
+        assert_eq!(8, paths.nodes().len());
+    }
+
+    #[test]
+    fn dijkstra_and_astar() {
+        // 0 -> 1 -> 3 costs 2 + 2, 0 -> 2 -> 3 costs 1 + 5 and 0 -> 3 costs 7.
+        let edges = |n: &u8| match n {
+            0 => vec![(1, 2u32), (2, 1), (3, 7)],
+            1 => vec![(3, 2)],
+            2 => vec![(3, 5)],
+            _ => vec![],
+        };
+
+        assert_eq!(
+            Some((vec![0, 1, 3], 4)),
+            super::dijkstra(0, edges, |n| *n == 3)
+        );
+        assert_eq!(
+            Some((vec![0, 1, 3], 4)),
+            super::astar(0, edges, |n| if *n == 3 { 0 } else { 1 }, |n| *n == 3)
+        );
+        assert_eq!(Some(&5), super::dijkstra_distances(2, edges).get(&3));
+        assert_eq!(None, super::dijkstra(3, edges, |n| *n == 0));
+    }
+}
@@ -0,0 +1,97 @@
+use std::{fmt, time::Duration};
+
+use serde::{Serialize, Serializer};
+
+use crate::solution::{Answer, Part};
+
+/// Answer and timings of a part, as shown in the end-of-run summary.
+#[derive(Debug, Serialize)]
+pub struct Row {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    #[serde(rename = "parse_ms", serialize_with = "as_millis")]
+    pub parse_time: Duration,
+    #[serde(rename = "solve_ms", serialize_with = "as_millis")]
+    pub solve_time: Duration,
+}
+
+fn as_millis<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+}
+
+/// Summary of every part run by the binary.
+#[derive(Debug, Default, Serialize)]
+#[serde(transparent)]
+pub struct Report {
+    rows: Vec<Row>,
+}
+
+impl Report {
+    pub fn push(&mut self, day: u8, part: Part, answer: &Answer) {
+        self.rows.push(Row {
+            day,
+            part,
+            answer: answer.value.clone(),
+            parse_time: answer.parse_time,
+            solve_time: answer.solve_time,
+        });
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let answer_width = self
+            .rows
+            .iter()
+            .map(|r| r.answer.len())
+            .max()
+            .unwrap_or(0)
+            .max("answer".len());
+
+        writeln!(
+            f,
+            "day | part | {:<answer_width$} | {:>10} | {:>10}",
+            "answer", "parse", "solve"
+        )?;
+        writeln!(
+            f,
+            "----+------+-{:-<answer_width$}-+-{:->10}-+-{:->10}",
+            "", "", ""
+        )?;
+
+        for row in &self.rows {
+            writeln!(
+                f,
+                " {:02} | {:>4} | {:<answer_width$} | {:>10} | {:>10}",
+                row.day,
+                u8::from(row.part),
+                row.answer,
+                format!("{:.2?}", row.parse_time),
+                format!("{:.2?}", row.solve_time),
+            )?;
+        }
+
+        let parse_total = self.rows.iter().map(|r| r.parse_time).sum::<Duration>();
+        let solve_total = self.rows.iter().map(|r| r.solve_time).sum::<Duration>();
+        writeln!(
+            f,
+            "----+------+-{:-<answer_width$}-+-{:->10}-+-{:->10}",
+            "", "", ""
+        )?;
+        write!(
+            f,
+            "    |      | {:<answer_width$} | {:>10} | {:>10}",
+            "total",
+            format!("{:.2?}", parse_total),
+            format!("{:.2?}", solve_total),
+        )
+    }
+}
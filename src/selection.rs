@@ -0,0 +1,122 @@
+use std::{fmt, ops::RangeInclusive, str::FromStr};
+
+use anyhow::{anyhow, bail};
+
+use crate::solution::{Day, DAYS};
+
+/// Days selected on the command line: a single day (`6`), an inclusive range (`1..13` or
+/// `1..=13`) or `all`.
+#[derive(Debug, Clone)]
+pub enum DaySelection {
+    All,
+    Days(RangeInclusive<u8>),
+}
+
+impl FromStr for DaySelection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(DaySelection::All);
+        }
+
+        let parse_day = |d: &str| {
+            let day = d
+                .trim()
+                .parse::<u8>()
+                .map_err(|_| anyhow!("invalid day `{}`", d))?;
+            if (1..=25).contains(&day) {
+                Ok(day)
+            } else {
+                Err(anyhow!("day {} is not between 1 and 25", day))
+            }
+        };
+
+        let range = if let Some((start, end)) = s.split_once("..") {
+            let end = end.strip_prefix('=').unwrap_or(end);
+            parse_day(start)?..=parse_day(end)?
+        } else {
+            let day = parse_day(s)?;
+            day..=day
+        };
+
+        if range.is_empty() {
+            bail!("empty day range `{}`", s);
+        }
+
+        Ok(DaySelection::Days(range))
+    }
+}
+
+impl DaySelection {
+    /// Registered days of the selection, failing if one of them is not implemented.
+    pub fn days(&self) -> anyhow::Result<Vec<&'static Day>> {
+        self.days_in(DAYS)
+    }
+
+    /// Days of the selection found in `registry`, sorted by day number.
+    fn days_in(&self, registry: &'static [Day]) -> anyhow::Result<Vec<&'static Day>> {
+        let get_day = |number| registry.iter().find(|d| d.number == number);
+
+        match self {
+            DaySelection::All => Ok(registry.iter().collect()),
+            DaySelection::Days(range) => {
+                let missing = range
+                    .clone()
+                    .filter(|d| get_day(*d).is_none())
+                    .collect::<Vec<_>>();
+
+                if missing.is_empty() {
+                    Ok(range.clone().filter_map(get_day).collect())
+                } else {
+                    Err(anyhow!("{} not implemented", DayList(&missing)))
+                }
+            }
+        }
+    }
+}
+
+struct DayList<'a>(&'a [u8]);
+
+impl fmt::Display for DayList<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            [day] => write!(f, "day {} is", day),
+            days => {
+                write!(f, "days ")?;
+                for (i, day) in days.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", day)?;
+                }
+                write!(f, " are")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DaySelection;
+    use crate::solution::DAYS;
+
+    #[test]
+    fn missing_days() {
+        let registry = &DAYS[..3];
+        let select = |s: &str| s.parse::<DaySelection>().unwrap().days_in(registry);
+
+        let days = select("2..3").expect("days 2 and 3 are registered");
+        assert_eq!(
+            vec![2, 3],
+            days.iter().map(|d| d.number).collect::<Vec<_>>()
+        );
+        assert_eq!(3, select("all").expect("all days are registered").len());
+
+        let err = select("4").err().expect("day 4 is not registered");
+        assert_eq!("day 4 is not implemented", err.to_string());
+
+        let err = select("2..=5").err().expect("days 4 and 5 are not registered");
+        assert_eq!("days 4, 5 are not implemented", err.to_string());
+    }
+}
@@ -0,0 +1,194 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+use serde::Serialize;
+
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+};
+
+/// Solver of a single day of the calendar.
+///
+/// The puzzle input is first parsed from its lines, then both parts solve the parsed input and
+/// return a displayable answer.
+pub trait Solution {
+    /// Day of the puzzle, between 1 and 25.
+    const DAY: u8;
+
+    /// Title of the puzzle.
+    const TITLE: &'static str;
+
+    /// Whether the puzzle has a second part to solve.
+    const HAS_PART_TWO: bool = true;
+
+    /// Puzzle input once parsed, shared by both parts.
+    type Input;
+
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn parse<I>(lines: I) -> anyhow::Result<Self::Input>
+    where
+        I: Iterator<Item = String>;
+
+    fn part_one(input: &Self::Input) -> anyhow::Result<Self::PartOne>;
+
+    fn part_two(input: &Self::Input) -> anyhow::Result<Self::PartTwo>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(into = "u8")]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl FromStr for Part {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(anyhow!("invalid part `{}`, expected `1` or `2`", s)),
+        }
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "part one"),
+            Part::Two => write!(f, "part two"),
+        }
+    }
+}
+
+/// Type-erased entry of the registry, built from a [`Solution`].
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    pub has_part_two: bool,
+    part_one: fn(&str) -> anyhow::Result<Answer>,
+    part_two: fn(&str) -> anyhow::Result<Answer>,
+}
+
+/// Answer of a part, along with the time spent to compute it.
+#[derive(Debug, Clone)]
+pub struct Answer {
+    pub value: String,
+    /// Time spent parsing the input.
+    pub parse_time: Duration,
+    /// Time spent solving the parsed input.
+    pub solve_time: Duration,
+}
+
+impl Day {
+    /// Registry entry of the solution `S`.
+    pub const fn new<S: Solution>() -> Self {
+        Self {
+            number: S::DAY,
+            title: S::TITLE,
+            has_part_two: S::HAS_PART_TWO,
+            part_one: run_part_one::<S>,
+            part_two: run_part_two::<S>,
+        }
+    }
+
+    /// Parts available for this day.
+    pub fn parts(&self) -> &'static [Part] {
+        if self.has_part_two {
+            &[Part::One, Part::Two]
+        } else {
+            &[Part::One]
+        }
+    }
+
+    /// Run a part on the whole input text.
+    pub fn run(&self, part: Part, input: &str) -> anyhow::Result<Answer> {
+        match part {
+            Part::One => (self.part_one)(input),
+            Part::Two if self.has_part_two => (self.part_two)(input),
+            Part::Two => Err(anyhow!("day {} has no part two", self.number)),
+        }
+    }
+}
+
+fn timed<S, T, F>(input: &str, solve: F) -> anyhow::Result<Answer>
+where
+    S: Solution,
+    T: Display,
+    F: FnOnce(&S::Input) -> anyhow::Result<T>,
+{
+    let lines = input.lines().map(str::to_owned).collect::<Vec<_>>();
+
+    let start = Instant::now();
+    let parsed = S::parse(lines.into_iter())?;
+    let parse_time = start.elapsed();
+
+    let start = Instant::now();
+    let value = solve(&parsed)?;
+    let solve_time = start.elapsed();
+
+    Ok(Answer {
+        value: value.to_string(),
+        parse_time,
+        solve_time,
+    })
+}
+
+fn run_part_one<S: Solution>(input: &str) -> anyhow::Result<Answer> {
+    timed::<S, _, _>(input, S::part_one)
+}
+
+fn run_part_two<S: Solution>(input: &str) -> anyhow::Result<Answer> {
+    timed::<S, _, _>(input, S::part_two)
+}
+
+/// Every implemented day, in calendar order.
+pub const DAYS: &[Day] = &[
+    Day::new::<day01::Day01>(),
+    Day::new::<day02::Day02>(),
+    Day::new::<day03::Day03>(),
+    Day::new::<day04::Day04>(),
+    Day::new::<day05::Day05>(),
+    Day::new::<day06::Day06>(),
+    Day::new::<day07::Day07>(),
+    Day::new::<day08::Day08>(),
+    Day::new::<day09::Day09>(),
+    Day::new::<day10::Day10>(),
+    Day::new::<day11::Day11>(),
+    Day::new::<day12::Day12>(),
+    Day::new::<day13::Day13>(),
+    Day::new::<day14::Day14>(),
+    Day::new::<day15::Day15>(),
+    Day::new::<day16::Day16>(),
+    Day::new::<day17::Day17>(),
+    Day::new::<day18::Day18>(),
+    Day::new::<day19::Day19>(),
+    Day::new::<day20::Day20>(),
+    Day::new::<day21::Day21>(),
+    Day::new::<day22::Day22>(),
+    Day::new::<day23::Day23>(),
+    Day::new::<day24::Day24>(),
+    Day::new::<day25::Day25>(),
+];
+
+/// Registered day with the given number, if it is implemented.
+pub fn get_day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
@@ -0,0 +1,59 @@
+use advent_of_code_2024::{
+    day01::Day01, day11, day18, selection::DaySelection, solution::get_day, Input, Part, Solution,
+    DAYS,
+};
+
+const DAY01_EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+#[test]
+fn registry_is_sorted_and_unique() {
+    assert!(DAYS.windows(2).all(|w| w[0].number < w[1].number));
+    assert!(DAYS.iter().all(|d| (1..=25).contains(&d.number)));
+}
+
+#[test]
+fn run_registered_day() {
+    let day = get_day(1).expect("day 1 is registered");
+
+    assert_eq!("11", day.run(Part::One, DAY01_EXAMPLE).unwrap().value);
+    assert_eq!("31", day.run(Part::Two, DAY01_EXAMPLE).unwrap().value);
+}
+
+#[test]
+fn run_solution_on_inline_input() {
+    let input = Input::Inline(DAY01_EXAMPLE.to_string());
+
+    assert_eq!(
+        11,
+        Day01::part_one(&Day01::parse(input.lines().unwrap()).unwrap()).unwrap()
+    );
+}
+
+#[test]
+fn select_days() {
+    let days = "1..3".parse::<DaySelection>().unwrap().days().unwrap();
+    assert_eq!(
+        vec![1, 2, 3],
+        days.iter().map(|d| d.number).collect::<Vec<_>>()
+    );
+
+    assert!("0".parse::<DaySelection>().is_err());
+    assert!("26".parse::<DaySelection>().is_err());
+    assert!("3..1".parse::<DaySelection>().is_err());
+    assert_eq!(
+        25,
+        "all".parse::<DaySelection>().unwrap().days().unwrap().len()
+    );
+}
+
+#[test]
+fn shared_helpers() {
+    let mut cache = Default::default();
+    assert_eq!(
+        22,
+        day11::expand::<6>(0, 125, &mut cache) + day11::expand::<6>(0, 17, &mut cache)
+    );
+
+    let bytes = day18::parse_bytes(["1,1".to_string()].into_iter(), 3).unwrap();
+    assert_eq!(4, day18::part_one(&bytes, 3).unwrap());
+}